@@ -0,0 +1,66 @@
+//! 键共享的基准测试
+//!
+//! 输入是 10000 个形状相同的对象组成的数组，每个对象都有同样的 5 个键。
+//! 分别用默认配置和打开 intern_keys 的配置解析为 json::Value，
+//! 先统计两种方式各自分配了多少次内存，再用 criterion 测量耗时。
+//! 关闭时每个键都分配一次，打开后每种键只分配一次，分配次数大约少 50000 次。
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use json_parser::Parser;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 统计分配次数的全局分配器，实际的分配交给 System
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// count 个形状相同的对象组成的数组
+fn uniform_objects(count: usize) -> String {
+    let items: Vec<String> = (0..count)
+        .map(|i| format!(r#"{{"id": {i}, "name": "user{i}", "active": true, "score": {}.5, "group": null}}"#, i % 100))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// 解析一次 input 期间发生的分配次数
+fn count_allocations(parser: &Parser, input: &str) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let value = parser.parse_native(input).unwrap();
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(value);
+    after - before
+}
+
+fn bench_intern(c: &mut Criterion) {
+    let input = uniform_objects(10_000);
+    let parsers = [("plain", Parser::new()), ("interned", Parser::new().intern_keys(true))];
+    for (name, parser) in &parsers {
+        println!("{}: {} 次分配", name, count_allocations(parser, &input));
+    }
+
+    let mut group = c.benchmark_group("uniform_objects");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    for (name, parser) in &parsers {
+        group.bench_function(*name, |b| b.iter(|| parser.parse_native(black_box(&input)).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_intern);
+criterion_main!(benches);
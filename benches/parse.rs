@@ -0,0 +1,66 @@
+//! 基准测试套件
+//!
+//! 覆盖几类典型输入，作为后续性能改动的比较基线：
+//! - 小对象：接近 API 请求体的大小，主要衡量固定开销
+//! - 数字数组：大量短小的数字，主要衡量数字解析和数组收集
+//! - 深层嵌套：每个值都在很深的位置，主要衡量递归和嵌套层数检查
+//! - 字符串为主：长短不一、部分含有转义的字符串，主要衡量字符串处理
+//!
+//! 每类输入都分别测量 parse、validate 和 minify。
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// 小对象，约 200 字节
+fn small_object() -> String {
+    r#"{"id": 42, "name": "Alice", "email": "alice@example.com", "active": true, "roles": ["admin", "dev"], "score": 98.5, "manager": null}"#.to_string()
+}
+
+/// 包含 count 个数字的扁平数组，整数和小数交替出现
+fn flat_numbers(count: usize) -> String {
+    let items: Vec<String> = (0..count)
+        .map(|i| if i % 2 == 0 { format!("{}", i * 7919) } else { format!("-{}.{}e3", i, i % 97) })
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// count 份嵌套 depth 层的结构组成的数组，对象和数组交替嵌套
+fn deep_nesting(count: usize, depth: usize) -> String {
+    let mut one = String::from("1");
+    for level in 0..depth {
+        one = if level % 2 == 0 { format!("[{}]", one) } else { format!("{{\"k\": {}}}", one) };
+    }
+    format!("[{}]", vec![one; count].join(", "))
+}
+
+/// 以字符串为主的对象数组，部分字符串含有转义
+fn string_heavy(count: usize) -> String {
+    let items: Vec<String> = (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"title": "Document number {i} with a reasonably long title", "body": "line one\nline two\t\"quoted\" caf\u00e9 {i}", "path": "C:\\data\\{i}.txt"}}"#
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn bench_suite(c: &mut Criterion) {
+    let inputs = [
+        ("small_object", small_object()),
+        ("flat_numbers", flat_numbers(100_000)),  // 约 1MB
+        ("deep_nesting", deep_nesting(2_000, 100)),  // 每份 100 层，低于默认的 max_depth
+        ("string_heavy", string_heavy(10_000)),  // 约 1.5MB
+    ];
+    for (name, input) in &inputs {
+        let mut group = c.benchmark_group(*name);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function("parse", |b| b.iter(|| json_parser::parse(black_box(input)).unwrap()));
+        group.bench_function("validate", |b| b.iter(|| json_parser::validate(black_box(input)).unwrap()));
+        group.bench_function("minify", |b| b.iter(|| json_parser::minify(black_box(input)).unwrap()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_suite);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+//! 解析大字符串的速度
+//!
+//! 分别测量不含转义的 1MB 字符串和大量转义的 1MB 字符串，
+//! 前者应当整段复制，后者才需要逐个处理转义序列。
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn bench_string(c: &mut Criterion) {
+    let plain = format!("\"{}\"", "abcdefghij".repeat(100_000));  // 约 1MB，没有转义
+    let escaped = format!("\"{}\"", "abcd\\n\\u00e9".repeat(83_334));  // 约 1MB，每 12 个字节两个转义
+    let mut group = c.benchmark_group("string_1mb");
+    group.bench_function("plain", |b| b.iter(|| json_parser::parse(black_box(&plain)).unwrap()));
+    group.bench_function("escaped", |b| b.iter(|| json_parser::parse(black_box(&escaped)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_string);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+//! 比较 validate 和 parse 的速度
+//!
+//! 输入是一个较大的对象数组，每个对象包含字符串、数字、布尔值和嵌套数组，
+//! parse 需要为每个字符串、数组和对象分配内存，validate 不需要。
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// 生成包含 count 个对象的 JSON 数组
+fn sample(count: usize) -> String {
+    let items: Vec<String> = (0..count)
+        .map(|i| format!(r#"{{"id": {i}, "name": "item number {i}", "price": {i}.25, "active": true, "tags": ["a", "b\n", "c"]}}"#))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let input = sample(10_000);  // 约 1MB
+    let mut group = c.benchmark_group("large_array");
+    group.bench_function("parse", |b| b.iter(|| json_parser::parse(black_box(&input)).unwrap()));
+    group.bench_function("validate", |b| b.iter(|| json_parser::validate(black_box(&input)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_validate);
+criterion_main!(benches);
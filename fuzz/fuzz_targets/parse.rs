@@ -0,0 +1,16 @@
+//! 把任意字节交给解析器，只要求不 panic
+//!
+//! 解析结果可以是 Ok 也可以是 Err，只要进程不崩溃就算通过。
+//! 同时检查 parse_bytes、validate 和 parse 对同一份输入的判断一致。
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = json_parser::parse_bytes(data);
+    if let Ok(text) = std::str::from_utf8(data) {
+        assert_eq!(json_parser::parse(text).is_ok(), parsed.is_ok());
+        assert_eq!(json_parser::validate(text).is_ok(), parsed.is_ok());  // 校验与解析的结论一致
+    }
+});
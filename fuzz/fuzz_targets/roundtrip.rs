@@ -0,0 +1,18 @@
+//! 解析 -> 序列化 -> 再解析，检查结果稳定
+//!
+//! 对能够解析的输入，to_string 的输出必须能被重新解析，并且得到相同的值；
+//! 再序列化一次得到的文本也必须与第一次完全相同。
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = json_parser::parse_bytes(data) else {
+        return;  // 只关心合法的文档
+    };
+    let text = json_parser::to_string(&value);
+    let reparsed = json_parser::parse(&text).expect("to_string 的输出应当能被重新解析");
+    assert_eq!(reparsed, value);
+    assert_eq!(json_parser::to_string(&reparsed), text);
+});
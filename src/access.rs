@@ -0,0 +1,102 @@
+//! 返回 Result 的取值函数
+//!
+//! serde_json::Value 的 as_str、as_i64 等方法返回 Option，
+//! 从文档里逐个取字段时，每一处都要自己构造错误信息。
+//! 这里的函数在类型不符时返回 TypeError，说明需要的类型和实际的类型，
+//! 可以直接用 ? 向上传递。
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// 值的类型与需要的类型不符
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    /// 需要的类型，如 "string"、"i64"
+    pub expected: &'static str,
+    /// 实际的类型，见 type_name
+    pub actual: &'static str,
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "类型不匹配：需要 {}，实际是 {}", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+/// 返回值的类型名，用于 TypeError::actual
+///
+/// 数字按能否用 i64 或 u64 表示细分为 "integer" 和 "float"，
+/// 其余类型为 "null"、"bool"、"string"、"array" 和 "object"
+pub(crate) fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "float",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// 由 Option 构造 Result，None 时的错误说明需要 expected 类型
+fn required<T>(found: Option<T>, value: &Value, expected: &'static str) -> Result<T, TypeError> {
+    found.ok_or(TypeError { expected, actual: type_name(value) })
+}
+
+/// 取字符串的内容
+///
+/// 举例：
+/// - 输入 "abc" -> Ok("abc")
+/// - 输入 1 -> Err，类型不匹配：需要 string，实际是 integer
+pub fn as_str_required(value: &Value) -> Result<&str, TypeError> {
+    required(value.as_str(), value, "string")
+}
+
+/// 取可以用 i64 表示的整数
+///
+/// 浮点数和超出 i64 范围的整数都会失败，
+/// 后者的 actual 也是 "integer"，此时 expected 的 "i64" 说明了范围的要求。
+///
+/// 举例：
+/// - 输入 -5 -> Ok(-5)
+/// - 输入 1.5 -> Err，类型不匹配：需要 i64，实际是 float
+pub fn as_i64_required(value: &Value) -> Result<i64, TypeError> {
+    required(value.as_i64(), value, "i64")
+}
+
+/// 取可以用 u64 表示的非负整数
+pub fn as_u64_required(value: &Value) -> Result<u64, TypeError> {
+    required(value.as_u64(), value, "u64")
+}
+
+/// 取任意数字并转换为 f64，大整数可能丢失精度
+///
+/// 举例：
+/// - 输入 2 -> Ok(2.0)
+/// - 输入 "2" -> Err，类型不匹配：需要 number，实际是 string
+pub fn as_f64_required(value: &Value) -> Result<f64, TypeError> {
+    required(value.as_f64(), value, "number")
+}
+
+/// 取布尔值
+pub fn as_bool_required(value: &Value) -> Result<bool, TypeError> {
+    required(value.as_bool(), value, "bool")
+}
+
+/// 取数组的元素
+pub fn as_array_required(value: &Value) -> Result<&Vec<Value>, TypeError> {
+    required(value.as_array(), value, "array")
+}
+
+/// 取对象的成员表
+///
+/// 举例：
+/// - 输入 {"a": 1} -> Ok，成员表中有键 "a"
+/// - 输入 null -> Err，类型不匹配：需要 object，实际是 null
+pub fn as_object_required(value: &Value) -> Result<&Map<String, Value>, TypeError> {
+    required(value.as_object(), value, "object")
+}
@@ -0,0 +1,117 @@
+//! 借用输入的 JSON 值
+//!
+//! serde_json::Value 中的每个字符串都是独立分配的 String。
+//! 解析结果只在输入还存活时短暂使用的话，这些复制是多余的。
+//! BorrowedValue 中的字符串和对象的键是 Cow<str>：
+//! 不含转义时直接借用输入中的原文，只有需要解码转义时才分配新的 String。
+//! 数字同样借用原文，需要时再转换。
+//!
+//! 这个模块需要打开 borrowed 特性。
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+#[cfg(feature = "serde_json")]
+use crate::build::number_from_str;
+use crate::build::{FromJson, is_representable};
+use crate::config::ParserConfig;
+use crate::{ParseError, parser};
+
+/// 借用输入的 JSON 值
+///
+/// 生命周期 'a 是输入文本的生命周期，值不能比输入活得更久。
+/// 需要脱离输入单独保存时，调用 into_value 转换为 serde_json::Value。
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedValue<'a> {
+    /// null
+    Null,
+    /// 布尔值
+    Bool(bool),
+    /// 数字在输入中的原文，已经确认是合法且可以表示的 JSON 数字
+    Number(&'a str),
+    /// 字符串，不含转义时借用输入
+    String(Cow<'a, str>),
+    /// 数组
+    Array(Vec<BorrowedValue<'a>>),
+    /// 对象，成员按键排序；与 serde_json::Map 不同，不保留文档中的顺序
+    Object(BTreeMap<Cow<'a, str>, BorrowedValue<'a>>),
+}
+
+impl<'a> BorrowedValue<'a> {
+    /// 如果是字符串则返回其内容
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BorrowedValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 转换为不再借用输入的 serde_json::Value
+    ///
+    /// 数字按默认配置转换，与 parse 得到的结果相同
+    #[cfg(feature = "serde_json")]
+    pub fn into_value(self) -> Value {
+        match self {
+            BorrowedValue::Null => Value::Null,
+            BorrowedValue::Bool(b) => Value::Bool(b),
+            BorrowedValue::Number(text) => number_from_str(text, &ParserConfig::default()).unwrap_or(Value::Null),  // 解析时已经确认可以表示
+            BorrowedValue::String(s) => Value::String(s.into_owned()),
+            BorrowedValue::Array(items) => Value::Array(items.into_iter().map(BorrowedValue::into_value).collect()),
+            BorrowedValue::Object(map) => Value::Object(
+                map.into_iter().map(|(key, item)| (key.into_owned(), item.into_value())).collect(),
+            ),
+        }
+    }
+}
+
+impl<'a> FromJson<'a> for BorrowedValue<'a> {
+    type Map = BTreeMap<Cow<'a, str>, BorrowedValue<'a>>;
+
+    fn null() -> Self {
+        BorrowedValue::Null
+    }
+
+    fn bool(b: bool) -> Self {
+        BorrowedValue::Bool(b)
+    }
+
+    fn number(text: &'a str, config: &ParserConfig) -> Option<Self> {
+        is_representable(text, config).then_some(BorrowedValue::Number(text))  // 只检查能否表示，保存原文
+    }
+
+    fn string(s: Cow<'a, str>) -> Self {
+        BorrowedValue::String(s)
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        BorrowedValue::Array(items)
+    }
+
+    fn new_map() -> Self::Map {
+        BTreeMap::new()
+    }
+
+    fn map_contains(map: &Self::Map, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn map_insert(map: &mut Self::Map, key: Cow<'a, str>, value: Self) {
+        map.insert(key, value);
+    }
+
+    fn object(map: Self::Map) -> Self {
+        BorrowedValue::Object(map)
+    }
+}
+
+/// 按指定配置解析为 BorrowedValue
+pub(crate) fn parse_with_config<'a>(input: &'a str, config: &ParserConfig) -> Result<BorrowedValue<'a>, ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 跳过开头的字节顺序标记
+    parser::parse_document(text, config)
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
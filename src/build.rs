@@ -0,0 +1,229 @@
+//! 解析结果的构造
+//!
+//! parser 模块里的语法规则不关心最终得到什么类型的值，
+//! 只在识别出 null、布尔值、数字、字符串、数组和对象时
+//! 调用 FromJson 中对应的方法构造结果。
+//! 这里为 serde_json::Value 实现这个 trait，其他输出类型各自在自己的模块里实现。
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+use crate::config::ParserConfig;
+use crate::json::Number;
+
+/// 可以由解析器构造的 JSON 值类型
+///
+/// 'a 是输入的生命周期，字符串和对象的键以 Cow 的形式传入：
+/// 不含转义时直接借用输入，含有转义时才是新分配的 String。
+/// 需要借用输入的输出类型可以原样保存 Cow，其他类型按需转换为 String。
+///
+/// 对象的成员先逐个插入 Map 中，全部解析完成后再由 object 转换为值，
+/// 重复键由解析器按配置处理，实现里只需要提供查找和插入。
+pub(crate) trait FromJson<'a>: Sized + Clone {
+    /// 对象在构造过程中使用的成员表
+    type Map;
+
+    /// 构造 null
+    fn null() -> Self;
+
+    /// 构造布尔值
+    fn bool(b: bool) -> Self;
+
+    /// 由已经通过语法检查的数字文本构造数字，无法表示时返回 None
+    fn number(text: &'a str, config: &ParserConfig) -> Option<Self>;
+
+    /// 由 number 构造出的 serde_json::Number，交给 Parser::with_number_validator 设置的校验函数
+    ///
+    /// 默认返回 None；只有 serde_json::Value 覆盖它，其他输出类型不调用校验函数
+    #[cfg(feature = "serde_json")]
+    fn serde_number(&self) -> Option<&serde_json::Number> {
+        None
+    }
+
+    /// 构造字符串
+    fn string(s: Cow<'a, str>) -> Self;
+
+    /// 由元素列表构造数组
+    fn array(items: Vec<Self>) -> Self;
+
+    /// 创建空的成员表
+    fn new_map() -> Self::Map;
+
+    /// 成员表中是否已经有这个键
+    fn map_contains(map: &Self::Map, key: &str) -> bool;
+
+    /// 向成员表中插入成员，键已存在时覆盖旧值
+    fn map_insert(map: &mut Self::Map, key: Cow<'a, str>, value: Self);
+
+    /// 打开 intern_keys 时用来插入成员，键先经过 keys 缓存
+    ///
+    /// 默认忽略缓存，与 map_insert 相同；键类型为 Rc<str> 的实现覆盖它来共享键
+    fn map_insert_interned(map: &mut Self::Map, key: Cow<'a, str>, value: Self, keys: &KeyCache) {
+        let _ = keys;
+        Self::map_insert(map, key, value);
+    }
+
+    /// 由成员表构造对象
+    fn object(map: Self::Map) -> Self;
+}
+
+#[cfg(feature = "serde_json")]
+impl<'a> FromJson<'a> for Value {
+    type Map = serde_json::Map<String, Value>;
+
+    fn null() -> Self {
+        Value::Null
+    }
+
+    fn bool(b: bool) -> Self {
+        Value::Bool(b)
+    }
+
+    fn number(text: &'a str, config: &ParserConfig) -> Option<Self> {
+        number_from_str(text, config)
+    }
+
+    fn serde_number(&self) -> Option<&serde_json::Number> {
+        self.as_number()
+    }
+
+    fn string(s: Cow<'a, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        Value::Array(items)
+    }
+
+    fn new_map() -> Self::Map {
+        serde_json::Map::new()
+    }
+
+    fn map_contains(map: &Self::Map, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn map_insert(map: &mut Self::Map, key: Cow<'a, str>, value: Self) {
+        map.insert(key.into_owned(), value);
+    }
+
+    fn object(map: Self::Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+/// 一次解析中已经出现过的对象键
+///
+/// 相同的键只分配一次，之后每次都返回同一个 Rc<str> 的克隆，
+/// 克隆只增加引用计数，不会再分配内存。缓存随解析结束一起释放。
+#[derive(Default)]
+pub(crate) struct KeyCache {
+    keys: RefCell<BTreeSet<Rc<str>>>,
+}
+
+impl KeyCache {
+    /// 返回与 key 内容相同的共享键，第一次出现时加入缓存
+    pub(crate) fn intern(&self, key: &str) -> Rc<str> {
+        let mut keys = self.keys.borrow_mut();
+        if let Some(shared) = keys.get(key) {
+            return Rc::clone(shared);  // 已经出现过，共享同一份
+        }
+        let shared: Rc<str> = Rc::from(key);
+        keys.insert(Rc::clone(&shared));
+        shared
+    }
+}
+
+/// 只检查语法、什么也不构造的输出类型
+///
+/// validate 模块用它复用 parser 模块里的标量解析器。
+/// 数字仍然要检查能否表示，保证与 parse 接受的输入完全一致。
+impl<'a> FromJson<'a> for () {
+    type Map = ();
+
+    fn null() -> Self {}
+
+    fn bool(_: bool) -> Self {}
+
+    fn number(text: &'a str, config: &ParserConfig) -> Option<Self> {
+        is_representable(text, config).then_some(())
+    }
+
+    fn string(_: Cow<'a, str>) -> Self {}
+
+    fn array(_: Vec<Self>) -> Self {}
+
+    fn new_map() -> Self::Map {}
+
+    fn map_contains(_: &Self::Map, _: &str) -> bool {
+        false  // 不记录键，也就不检查重复键
+    }
+
+    fn map_insert(_: &mut Self::Map, _: Cow<'a, str>, _: Self) {}
+
+    fn object(_: Self::Map) -> Self {}
+}
+
+/// 检查已经通过语法检查的数字文本能否表示为 JSON 数字
+///
+/// 规则与 number_from_str 相同：打开 arbitrary_precision 时任何数字都可以保存，
+/// 否则结果不能是无穷大。用于不需要真正转换数字的输出类型。
+pub(crate) fn is_representable(text: &str, config: &ParserConfig) -> bool {
+    #[cfg(feature = "arbitrary_precision")]
+    if config.arbitrary_precision {
+        return true;  // 原样保存数字文本
+    }
+    #[cfg(not(feature = "arbitrary_precision"))]
+    let _ = config;  // 未启用 arbitrary_precision 特性时没有用到配置
+    Number::from_text(text).is_some()
+}
+
+/// 将识别出的数字字符串转换为 JSON 数字
+/// 
+/// 转换过程：
+/// - 如果字符串中没有 '.'、'e'、'E'，先尝试解析为 i64，再尝试 u64，
+///   这样大整数（如 9007199254740993）不会因为经过 f64 而丢失精度
+/// - 否则（或整数超出 u64 范围）将字符串解析为 f64 类型的浮点数
+/// - 然后转换为 serde_json::Number 类型
+/// - 最后包装为 JSON Value 类型
+/// 
+/// 打开 arbitrary_precision 时，直接保存原始数字文本，不经过任何转换，
+/// 因此 "1234567890.123456789012345" 这样的数字一位都不会丢失
+/// 
+/// 无法表示的数字（如溢出为无穷大）返回 None
+#[cfg(feature = "serde_json")]
+pub(crate) fn number_from_str(s: &str, config: &ParserConfig) -> Option<Value> {
+    // 0: 任意精度模式下原样保存数字文本
+    #[cfg(feature = "arbitrary_precision")]
+    if config.arbitrary_precision {
+        return Some(Value::Number(serde_json::Number::from_string_unchecked(s.to_string())));
+    }
+    #[cfg(not(feature = "arbitrary_precision"))]
+    let _ = config;  // 未启用 arbitrary_precision 特性时没有用到配置
+
+    // 1: 没有小数点和指数时，优先按整数解析
+    // 例如："42" -> 42，"-7" -> -7，"18446744073709551615" -> u64::MAX
+    if !s.contains(['.', 'e', 'E']) {
+        if let Ok(i) = s.parse::<i64>() {
+            return Some(Value::Number(serde_json::Number::from(i)));
+        }
+        if let Ok(u) = s.parse::<u64>() {
+            return Some(Value::Number(serde_json::Number::from(u)));
+        }
+    }
+
+    // 2: 将字符串解析为 f64 类型的浮点数
+    // 例如："123.45" -> 123.45
+    let num = s.parse::<f64>().ok()?;
+
+    // 3: 将 f64 转换为 serde_json 的 Number 类型
+    // 这一步确保数字符合 JSON 标准，无穷大和 NaN 会得到 None
+    // 例如：123.45 -> serde_json::Number
+    serde_json::Number::from_f64(num).map(Value::Number)
+}
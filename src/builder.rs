@@ -0,0 +1,261 @@
+//! 构建器风格的解析器
+//!
+//! 各项配置都集中在 Parser 上，通过链式调用逐项设置，
+//! 例如 Parser::new().max_depth(64).allow_comments(true).parse(input)。
+//! 不需要调整配置时，直接使用顶层的 parse 函数即可。
+
+#[cfg(feature = "serde_json")]
+use alloc::string::String;
+#[cfg(feature = "serde_json")]
+use alloc::sync::Arc;
+#[cfg(feature = "serde_json")]
+use core::fmt;
+
+#[cfg(feature = "serde_json")]
+use serde_json::{Number, Value};
+
+use crate::config::{DuplicateKeys, InvalidEscapePolicy, ParserConfig};
+use crate::{ParseError, json};
+
+/// 可配置的 JSON 解析器
+///
+/// 内部保存一份 ParserConfig，每个设置方法都返回修改后的 Parser，
+/// 最后调用 parse 按这份配置解析。同一个 Parser 可以反复使用。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parser {
+    config: ParserConfig,
+    /// with_number_validator 设置的校验函数
+    #[cfg(feature = "serde_json")]
+    number_validator: Option<NumberValidator>,
+}
+
+/// 包装用户提供的数字校验函数
+///
+/// 闭包本身无法比较和打印，两个 NumberValidator 只有指向同一个闭包时才相等
+#[cfg(feature = "serde_json")]
+#[derive(Clone)]
+struct NumberValidator(Arc<NumberValidatorFn>);
+
+/// 数字校验函数的类型，返回 Err 时附带拒绝的原因
+#[cfg(feature = "serde_json")]
+type NumberValidatorFn = dyn Fn(&Number) -> Result<(), String> + Send + Sync;
+
+#[cfg(feature = "serde_json")]
+impl fmt::Debug for NumberValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NumberValidator(..)")
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl PartialEq for NumberValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl Eq for NumberValidator {}
+
+impl Parser {
+    /// 创建使用默认配置的解析器（严格的 JSON 语法）
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// 设置数组和对象允许嵌套的最大层数
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.config.max_depth = max_depth;
+        self
+    }
+
+    /// 设置是否允许 // 和 /* */ 注释
+    pub fn allow_comments(mut self, allow: bool) -> Self {
+        self.config.allow_comments = allow;
+        self
+    }
+
+    /// 设置是否允许数组和对象末尾的尾随逗号
+    pub fn allow_trailing_comma(mut self, allow: bool) -> Self {
+        self.config.allow_trailing_comma = allow;
+        self
+    }
+
+    /// 设置是否打开 JSON5 风格的单引号字符串和不带引号的键
+    pub fn json5(mut self, enable: bool) -> Self {
+        self.config.json5 = enable;
+        self
+    }
+
+    /// 设置是否接受 NaN、Infinity 和 -Infinity
+    pub fn allow_nan(mut self, allow: bool) -> Self {
+        self.config.allow_nan = allow;
+        self
+    }
+
+    /// 设置是否按任意精度保存数字
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn arbitrary_precision(mut self, enable: bool) -> Self {
+        self.config.arbitrary_precision = enable;
+        self
+    }
+
+    /// 设置是否把小数按 Decimal 精确保存，只对 parse_native 的结果起作用
+    #[cfg(feature = "rust_decimal")]
+    pub fn decimal(mut self, enable: bool) -> Self {
+        self.config.decimal = enable;
+        self
+    }
+
+    /// 设置是否拒绝经过 f64 之后无法还原的数字
+    pub fn reject_lossy_numbers(mut self, reject: bool) -> Self {
+        self.config.reject_lossy_numbers = reject;
+        self
+    }
+
+    /// 设置对象中出现重复键时的处理方式
+    pub fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.config.duplicate_keys = policy;
+        self
+    }
+
+    /// 设置整个文档中最多允许解析出多少个值
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.config.max_elements = Some(max_elements);
+        self
+    }
+
+    /// 设置单个字符串解码后最多允许多少字节
+    pub fn max_string_len(mut self, max_string_len: usize) -> Self {
+        self.config.max_string_len = Some(max_string_len);
+        self
+    }
+
+    /// 设置单个数字在输入中最多允许多少个字符
+    pub fn max_number_len(mut self, max_number_len: usize) -> Self {
+        self.config.max_number_len = Some(max_number_len);
+        self
+    }
+
+    /// 设置单个对象最多允许有多少个成员
+    pub fn max_object_entries(mut self, max_object_entries: usize) -> Self {
+        self.config.max_object_entries = Some(max_object_entries);
+        self
+    }
+
+    /// 设置单个数组最多允许有多少个元素
+    pub fn max_array_entries(mut self, max_array_entries: usize) -> Self {
+        self.config.max_array_entries = Some(max_array_entries);
+        self
+    }
+
+    /// 设置是否把空输入解析为 null
+    pub fn empty_as_null(mut self, enable: bool) -> Self {
+        self.config.empty_as_null = enable;
+        self
+    }
+
+    /// 设置是否共享重复出现的对象键，只对 parse_native 的结果起作用
+    pub fn intern_keys(mut self, enable: bool) -> Self {
+        self.config.intern_keys = enable;
+        self
+    }
+
+    /// 设置是否按键的顺序把对象成员插入 Map
+    pub fn sort_keys(mut self, enable: bool) -> Self {
+        self.config.sort_keys = enable;
+        self
+    }
+
+    /// 设置字符串中出现无法识别的转义时的处理方式
+    pub fn invalid_escape(mut self, policy: InvalidEscapePolicy) -> Self {
+        self.config.invalid_escape = policy;
+        self
+    }
+
+    /// 设置是否要求顶层的值必须是对象或数组
+    pub fn require_root_container(mut self, require: bool) -> Self {
+        self.config.require_root_container = require;
+        self
+    }
+
+    /// 设置整个输入最多允许多少字节
+    pub fn max_input_bytes(mut self, max_input_bytes: usize) -> Self {
+        self.config.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+
+    /// 设置是否不区分大小写地接受 true、false 和 null
+    pub fn case_insensitive_literals(mut self, enable: bool) -> Self {
+        self.config.case_insensitive_literals = enable;
+        self
+    }
+
+    /// 设置数字的校验函数，parse 每解析出一个数字就调用它一次
+    ///
+    /// 校验在解析的同一遍中完成，不需要对结果再遍历一次。
+    /// validator 返回 Err(reason) 时解析立即停止，返回 RejectedNumber(reason) 错误，
+    /// 位置指向这个数字的开头。数组元素、对象成员的值和顶层的数字都会经过校验，
+    /// 对象的键不是数字，不受影响。
+    ///
+    /// 只对 parse 起作用，parse_native 和 parse_borrowed 的数字类型不同，不会调用它。
+    ///
+    /// 举例：只接受 i32 范围内的整数
+    ///
+    /// ```
+    /// use json_parser::{ErrorKind, Parser};
+    ///
+    /// let parser = Parser::new().with_number_validator(|n| match n.as_i64() {
+    ///     Some(i) if i32::try_from(i).is_ok() => Ok(()),
+    ///     _ => Err(format!("{} 不是 i32", n)),
+    /// });
+    /// assert!(parser.parse("[1, -2147483648]").is_ok());
+    /// let err = parser.parse("[1, 2147483648]").unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::RejectedNumber("2147483648 不是 i32".to_string()));
+    /// assert_eq!(err.offset, 4);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn with_number_validator(mut self, validator: impl Fn(&Number) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        self.number_validator = Some(NumberValidator(Arc::new(validator)));
+        self
+    }
+
+    /// 返回当前的配置
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
+    /// 按当前配置解析 JSON 文本
+    #[cfg(feature = "serde_json")]
+    pub fn parse(&self, input: &str) -> Result<Value, ParseError> {
+        let Some(NumberValidator(validator)) = &self.number_validator else {
+            return crate::parse_with_config(input, &self.config);
+        };
+        let ctx = crate::parser::Context::with_number_validator(&self.config, &**validator);
+        let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 parse 一样跳过字节顺序标记
+        crate::parser::parse_document_in(text, &ctx)
+            .map(|(_, value)| value)
+            .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+    }
+
+    /// 按当前配置解析 JSON 文本，返回不依赖 serde_json 的 json::Value
+    pub fn parse_native(&self, input: &str) -> Result<json::Value, ParseError> {
+        json::parse_with_config(input, &self.config)
+    }
+
+    /// 按当前配置解析 JSON 文本，字符串尽量借用输入
+    #[cfg(feature = "borrowed")]
+    pub fn parse_borrowed<'a>(&self, input: &'a str) -> Result<crate::BorrowedValue<'a>, ParseError> {
+        crate::borrowed::parse_with_config(input, &self.config)
+    }
+}
+
+impl From<ParserConfig> for Parser {
+    fn from(config: ParserConfig) -> Self {
+        Parser {
+            config,
+            #[cfg(feature = "serde_json")]
+            number_validator: None,
+        }
+    }
+}
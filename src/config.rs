@@ -0,0 +1,277 @@
+//! 解析配置
+//!
+//! ParserConfig 保存解析过程中需要的各项限制和开关，
+//! 由入口函数一路传给各个解析器。
+
+/// 默认的最大嵌套层数
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// 对象中出现重复键时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// 后出现的值覆盖先出现的值（默认，与 serde_json 一致）
+    ///
+    /// serde_json::Map 中这个键仍留在第一次出现的位置
+    #[default]
+    LastWins,
+    /// 保留先出现的值，忽略后面的
+    FirstWins,
+    /// 返回 DuplicateKey 错误
+    ///
+    /// 不同系统对重复键的处理方式不同，可能被利用来绕过校验，
+    /// 对安全敏感的场景应该使用这个选项
+    Error,
+}
+
+/// 字符串中出现无法识别的转义（如 \x）时的处理方式
+///
+/// 只针对反斜杠后面跟着不认识的字符的情况，格式错误的 \uXXXX（十六进制数字不足、
+/// 单独的代理项）、被输入末尾截断的转义序列，以及反斜杠后面的控制字符（如换行）总是报错，
+/// 字符串中的控制字符不能借由反斜杠混进来。
+///
+/// 举例（字符串 "\x41"）：
+/// - Error -> 失败，InvalidEscape
+/// - KeepLiteral -> "x41"
+/// - Replace -> "\u{FFFD}41"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidEscapePolicy {
+    /// 返回 InvalidEscape 错误（默认，与 RFC 8259 一致）
+    #[default]
+    Error,
+    /// 去掉反斜杠，保留后面的字符
+    KeepLiteral,
+    /// 把整个转义序列替换为 U+FFFD 替换字符
+    Replace,
+}
+
+/// 解析配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// 数组和对象允许嵌套的最大层数
+    ///
+    /// 解析器通过递归处理嵌套结构，不加限制时，
+    /// 像 100000 个连续的 '[' 这样的输入会耗尽调用栈导致进程崩溃。
+    /// 超过这个层数时返回 DepthExceeded 错误。
+    ///
+    /// 即使在未优化的构建中每一层也只占用几 KiB 的栈，默认的 128 层可以放进 2 MiB 的线程栈（例如测试线程）。
+    /// 调高这个值时要确认线程栈足够大，需要任意深度时使用 parse_iterative。
+    pub max_depth: usize,
+
+    /// 是否允许注释
+    ///
+    /// 打开后，凡是允许出现空白的地方都可以出现 "// 行注释" 和 "/* 块注释 */"，
+    /// 包括数组元素之间和对象成员之间，方便解析手写的配置文件。
+    /// 默认关闭，此时注释按 RFC 8259 视为语法错误。
+    pub allow_comments: bool,
+
+    /// 是否允许尾随逗号
+    ///
+    /// 打开后，数组最后一个元素和对象最后一个成员之后可以多一个逗号，
+    /// 例如 [1,2,] 和 {"a":1,}。默认关闭，此时尾随逗号是语法错误。
+    pub allow_trailing_comma: bool,
+
+    /// 是否打开 JSON5 风格的宽松语法
+    ///
+    /// 打开后：
+    /// - 字符串可以用单引号包围（如 'hello'），单引号字符串中可以用 \' 转义单引号
+    /// - 对象的键可以是不带引号的标识符（如 { name: "John" }）
+    ///
+    /// 默认关闭，此时只接受标准的双引号字符串，键也必须带引号。
+    pub json5: bool,
+
+    /// 是否接受 NaN、Infinity 和 -Infinity
+    ///
+    /// serde_json::Number 无法表示这些值，打开后它们都解析为 Value::Null。
+    /// 默认关闭，此时这三个字面量是语法错误。
+    pub allow_nan: bool,
+
+    /// 是否按任意精度保存数字
+    ///
+    /// 打开后，数字不再转换为 i64、u64 或 f64，而是原样保存识别出的数字文本，
+    /// 例如 1234567890.123456789012345 的每一位都会保留下来，输出时也按原文输出。
+    /// 依赖 serde_json 的 arbitrary_precision 特性，需要启用本库的 arbitrary_precision 特性。
+    #[cfg(feature = "arbitrary_precision")]
+    pub arbitrary_precision: bool,
+
+    /// 是否把小数按 rust_decimal::Decimal 精确保存
+    ///
+    /// 打开后，json::Value 中带小数点或指数的数字不再转换为 f64，
+    /// 而是保存为 Value::Decimal，1000.50 和 0.1 这样的十进制数不会有任何舍入，
+    /// 适合处理金额。整数仍然按 Number 保存。
+    /// Decimal 最多有 28 位有效数字，放不下的数字（如 1e100）退回为 Number::Float。
+    /// 只对 json::Value 起作用，需要启用本库的 rust_decimal 特性。
+    #[cfg(feature = "rust_decimal")]
+    pub decimal: bool,
+
+    /// 是否拒绝经过 f64 之后无法还原的数字
+    ///
+    /// 很多语言（如 JavaScript）只用 f64 表示数字，超过 2^53 的整数会悄悄变成相邻的另一个数。
+    /// 打开后，数字转换为 f64 再按最短形式输出时，如果不等于原来的值，就返回 LossyNumber 错误，
+    /// 例如 9007199254740993（2^53 + 1）会被拒绝，而 0.1、1e300 和 9007199254740992 都可以接受。
+    /// 2^64 这样虽然是 f64 却输出为 18446744073709552000 的大整数同样会被拒绝。
+    /// 检查只看数字文本本身，与 arbitrary_precision 等配置无关。默认关闭。
+    pub reject_lossy_numbers: bool,
+
+    /// 对象中出现重复键时的处理方式，默认后出现的值覆盖先出现的值
+    pub duplicate_keys: DuplicateKeys,
+
+    /// 整个文档中最多允许解析出多少个值
+    ///
+    /// 每个标量、数组和对象都算一个值，例如 [1, [2]] 共有 4 个值。
+    /// 像几百万个元素的扁平数组这样的输入，嵌套层数很浅却会占用大量内存，
+    /// max_depth 防不住，可以用这个选项限制。超过时返回 ResourceLimit 错误。
+    /// 默认为 None，不限制。
+    pub max_elements: Option<usize>,
+
+    /// 单个字符串（包括对象的键）解码后最多允许多少字节
+    ///
+    /// 一个几 GB 的字符串字面量就足以耗尽内存。
+    /// 解析器每追加一段内容就检查一次长度，超过时立即返回 ResourceLimit 错误，
+    /// 不会先把整个字符串读完。默认为 None，不限制。
+    pub max_string_len: Option<usize>,
+
+    /// 单个数字在输入中最多允许多少个字符
+    ///
+    /// 包括负号、小数点和指数部分，例如 -1.5e10 共 7 个字符。
+    /// 几百万位的数字在转换时会消耗大量 CPU，
+    /// 解析器识别出数字之后、转换之前检查长度，超过时返回 ResourceLimit 错误。
+    /// 默认为 None，不限制。
+    pub max_number_len: Option<usize>,
+
+    /// 单个对象最多允许有多少个成员
+    ///
+    /// 与限制整个文档的 max_elements 不同，这里只看一个对象，
+    /// 用来防御一个对象里塞进大量键的输入（例如针对哈希表的碰撞攻击）。
+    /// 重复的键也各算一个成员。超过时返回 ResourceLimit 错误，位置指向第一个超出限制的键。
+    /// 默认为 None，不限制。
+    pub max_object_entries: Option<usize>,
+
+    /// 单个数组最多允许有多少个元素
+    ///
+    /// 超过时返回 ResourceLimit 错误，位置指向第一个超出限制的元素。
+    /// 默认为 None，不限制。
+    pub max_array_entries: Option<usize>,
+
+    /// 是否把空输入解析为 null
+    ///
+    /// 有些 HTTP 客户端把空的响应体当作 null 处理。
+    /// 打开后，空字符串和只有空白（允许注释时也包括只有注释）的输入返回 Ok(null)；
+    /// 默认关闭，此时返回 EmptyInput 错误。
+    pub empty_as_null: bool,
+
+    /// 是否在一次解析中共享重复出现的对象键
+    ///
+    /// 由形状相同的对象组成的数组会把同样的键重复成千上万次，
+    /// 打开后解析器内部维护一张键的缓存，相同的键只分配一次，之后都共享同一个 Rc<str>。
+    /// 只对键类型为 Rc<str> 的 json::Value 起作用（见 Parser::parse_native），
+    /// serde_json::Value 的键必须是独立的 String，打开与否结果都一样。
+    /// 默认关闭。
+    pub intern_keys: bool,
+
+    /// 是否按键的顺序把对象成员插入 Map
+    ///
+    /// 打开后，每个对象的成员先按键排序（字节序，与 to_string_canonical 相同），再依次插入，
+    /// 重复的键之间仍保持原来的先后，duplicate_keys 的行为不变。
+    /// 与 to_string_canonical 不同，这里影响的是内存中的 Value 本身。
+    ///
+    /// 本库打开了 serde_json 的 preserve_order 特性，serde_json::Map 按插入顺序保存，
+    /// 默认得到的是成员在文档中的顺序，打开这个选项后得到按键排序的顺序。
+    /// BorrowedValue 和 json::Value 的对象本来就按键排序，打开与否结果都一样。
+    /// 默认关闭。
+    pub sort_keys: bool,
+
+    /// 字符串中出现无法识别的转义时的处理方式
+    ///
+    /// 一些工具输出的字符串里会有 \x、\a 这样不合法的转义，
+    /// 宽松地解析这类输入时，可以选择保留原字符或者替换为 U+FFFD，而不是整个文档解析失败。
+    /// 默认为 Error。
+    pub invalid_escape: InvalidEscapePolicy,
+
+    /// 是否要求顶层的值必须是对象或数组
+    ///
+    /// RFC 8259 允许任意 JSON 值出现在顶层，所以 42 和 "hi" 本身就是合法的文档，
+    /// 早先的 RFC 4627 则只允许对象和数组，一些消费方至今仍按这个规则处理。
+    /// 打开后，顶层是标量时返回 RootNotContainer 错误；嵌套在容器里的标量不受影响。
+    /// 空输入仍按 empty_as_null 处理。
+    /// 默认关闭，接受顶层的标量。
+    pub require_root_container: bool,
+
+    /// 整个输入最多允许多少字节，None 表示不限制
+    ///
+    /// 在开始解析之前就检查输入的长度，超过时直接返回 ResourceLimit，不做任何解析，
+    /// 比 max_elements 等逐项计数的限制更简单，适合按请求限制工作量的服务端。
+    /// 开头的字节顺序标记不计入。
+    /// StreamParser 用它限制缓冲区中尚未取出的输入，在追加输入时检查。
+    pub max_input_bytes: Option<usize>,
+
+    /// 是否不区分大小写地接受 true、false 和 null
+    ///
+    /// 有些不规范的程序会输出 TRUE、False、Null 这样的字面量，
+    /// 打开后它们分别按 true、false、null 解析，便于接收来源不统一的数据。
+    /// 字面量仍然必须完整，例如 "tru" 和 "nil" 依然是错误。
+    /// 默认关闭，与 RFC 8259 一致，只接受全部小写的写法。
+    pub case_insensitive_literals: bool,
+}
+
+impl ParserConfig {
+    /// 严格的配置，适合解析来源不可信的输入
+    ///
+    /// 语法与默认配置相同，只接受 RFC 8259 规定的 JSON，
+    /// 另外把 duplicate_keys 设为 Error，遇到重复的键直接报错。
+    ///
+    /// RFC 8259 只说对象的键“应该”唯一，重复时各个实现的处理方式并不一致：
+    /// 有的保留第一个值，有的保留最后一个。两个系统对同一份文档读出不同的值，
+    /// 就可能被利用来绕过校验（先由网关检查 {"role": "user", "role": "admin"} 中的第一个，
+    /// 后端却使用第二个）。所以这里有意拒绝重复的键，而不是悄悄按后出现的值覆盖。
+    /// 需要兼容已有的数据时，可以改用默认配置或把 duplicate_keys 设为 LastWins。
+    pub fn strict() -> Self {
+        ParserConfig {
+            duplicate_keys: DuplicateKeys::Error,
+            ..ParserConfig::default()
+        }
+    }
+
+    /// 宽松的配置，适合解析手写的配置文件
+    ///
+    /// 在默认配置的基础上打开 allow_comments、allow_trailing_comma 和 json5，
+    /// 即允许注释、尾随逗号、单引号字符串和不带引号的键。
+    /// NaN 和无穷大仍然不接受，其他限制与默认配置相同。
+    pub fn lenient() -> Self {
+        ParserConfig {
+            allow_comments: true,
+            allow_trailing_comma: true,
+            json5: true,
+            ..ParserConfig::default()
+        }
+    }
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            max_depth: DEFAULT_MAX_DEPTH,
+            allow_comments: false,
+            allow_trailing_comma: false,
+            json5: false,
+            allow_nan: false,
+            #[cfg(feature = "arbitrary_precision")]
+            arbitrary_precision: false,
+            #[cfg(feature = "rust_decimal")]
+            decimal: false,
+            reject_lossy_numbers: false,
+            duplicate_keys: DuplicateKeys::LastWins,
+            max_elements: None,
+            max_string_len: None,
+            max_number_len: None,
+            max_object_entries: None,
+            max_array_entries: None,
+            empty_as_null: false,
+            intern_keys: false,
+            sort_keys: false,
+            invalid_escape: InvalidEscapePolicy::Error,
+            require_root_container: false,
+            max_input_bytes: None,
+            case_insensitive_literals: false,
+        }
+    }
+}
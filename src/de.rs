@@ -0,0 +1,31 @@
+//! 反序列化为任意类型
+//!
+//! 用这个库的解析器作为前端，把 JSON 文本直接转换为实现了 Deserialize 的类型。
+
+use serde::de::DeserializeOwned;
+
+use crate::{ErrorKind, ParseError, parse};
+
+/// 解析 JSON 文本并反序列化为 T
+///
+/// 实现方式：先用 parse 解析为 serde_json::Value，
+/// 再交给 serde_json::from_value 转换为 T。
+/// 语法错误与 parse 完全相同，带有准确的行号和列号；
+/// 语法正确但类型不匹配（如缺少字段、字段类型不对）时返回 Deserialize 错误，
+/// 由于此时已经没有原文位置信息，位置总是第 1 行第 1 列（偏移量 0），source_line 为空。
+///
+/// 举例：
+/// - from_str::<Vec<i32>>("[1, 2]") -> 成功，返回 vec![1, 2]
+/// - from_str::<Vec<i32>>("[1, \"a\"]") -> 失败，返回 Deserialize 错误
+/// - from_str::<Vec<i32>>("[1, 2") -> 失败，返回 UnterminatedArray，位置指向开始方括号
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, ParseError> {
+    let value = parse(input)?;
+    serde_json::from_value(value).map_err(|e| ParseError {
+        kind: ErrorKind::Deserialize(e.to_string()),
+        line: 1,
+        column: 1,
+        offset: 0,
+        source_line: String::new(),
+        incomplete: false,
+    })
+}
@@ -0,0 +1,25 @@
+//! 嵌套深度
+//!
+//! 统计解析出的 Value 中数组和对象的最大嵌套层数，
+//! 计数方式与 ParserConfig::max_depth 相同。
+
+use serde_json::Value;
+
+/// 返回 value 中数组和对象的最大嵌套层数
+///
+/// 标量的深度是 0，数组或对象的深度是其中最深的子节点的深度加 1，
+/// 空数组和空对象的深度是 1。
+/// 按默认配置解析成功的文档，深度不会超过 max_depth。
+///
+/// 举例：
+/// - 输入 1 -> 0
+/// - 输入 [1] -> 1
+/// - 输入 [[1]] -> 2
+/// - 输入 {"a": [1, {"b": {}}], "c": 2} -> 4
+pub fn max_depth(value: &Value) -> usize {
+    match value {
+        Value::Array(items) => 1 + items.iter().map(max_depth).max().unwrap_or(0),
+        Value::Object(map) => 1 + map.values().map(max_depth).max().unwrap_or(0),
+        _ => 0,  // 标量没有嵌套
+    }
+}
@@ -0,0 +1,113 @@
+//! 比较 JSON 文档
+//!
+//! 逐层比较两个 Value，列出从前者变成后者所需的修改，
+//! 每处修改的位置用 JSON Pointer 字符串表示。
+
+use serde_json::Value;
+
+use crate::pointer::escape_token;
+
+/// 两个文档之间的一处差异
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// 新文档在 path 处多出了 value
+    Added {
+        /// 新增值的位置
+        path: String,
+        /// 新增的值
+        value: Value,
+    },
+    /// 旧文档在 path 处的 value 被删除了
+    Removed {
+        /// 被删除的值的位置
+        path: String,
+        /// 被删除的值
+        value: Value,
+    },
+    /// path 处的值从 before 变成了 after
+    Replaced {
+        /// 发生变化的位置
+        path: String,
+        /// 旧文档中的值
+        before: Value,
+        /// 新文档中的值
+        after: Value,
+    },
+}
+
+impl Change {
+    /// 返回这处差异的位置
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } | Change::Removed { path, .. } | Change::Replaced { path, .. } => path,
+        }
+    }
+}
+
+/// 列出从 a 变成 b 的所有差异
+///
+/// 详细解释：
+/// 1. 两边都是对象时按键比较：
+///    - 只在 a 中出现的键：Removed
+///    - 只在 b 中出现的键：Added
+///    - 两边都有的键：递归比较对应的值
+/// 2. 两边都是数组时按下标比较：
+///    - 共同的下标递归比较
+///    - a 更长时，多出的元素依次是 Removed；b 更长时，多出的元素依次是 Added
+/// 3. 其他情况下（标量，或者类型不同），两个值不相等时整体记为 Replaced
+///
+/// 完全相同的两个文档返回空列表。同一个对象中，
+/// 先按 a 的键顺序列出删除和修改，再列出 b 中新增的键。
+///
+/// 举例：
+/// - a {"x": 1, "y": 2}，b {"x": 1, "z": 3} -> Removed /y 以及 Added /z
+/// - a [1, 2, 3]，b [1, 5] -> Replaced /1（2 变成 5）以及 Removed /2
+/// - a {"a": [1]}，b {"a": {}} -> Replaced /a，类型不同时不再深入比较
+pub fn diff(a: &Value, b: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut path = String::new();  // 当前位置的 JSON Pointer，随递归增减
+    diff_at(&mut changes, &mut path, a, b);
+    changes
+}
+
+/// 比较 path 处的 a 和 b，把差异追加到 changes 末尾
+fn diff_at(changes: &mut Vec<Change>, path: &mut String, a: &Value, b: &Value) {
+    match (a, b) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, old) in before {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match after.get(key) {
+                    Some(new) => diff_at(changes, path, old, new),  // 两边都有，递归比较
+                    None => changes.push(Change::Removed { path: path.clone(), value: old.clone() }),
+                }
+                path.truncate(len);  // 回到当前对象
+            }
+            for (key, new) in after {
+                if !before.contains_key(key) {
+                    let path = format!("{}/{}", path, escape_token(key));
+                    changes.push(Change::Added { path, value: new.clone() });
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for (index, (old, new)) in before.iter().zip(after).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{}", index));
+                diff_at(changes, path, old, new);  // 共同的下标递归比较
+                path.truncate(len);
+            }
+            for (index, old) in before.iter().enumerate().skip(after.len()) {
+                let path = format!("{}/{}", path, index);
+                changes.push(Change::Removed { path, value: old.clone() });  // a 多出的元素
+            }
+            for (index, new) in after.iter().enumerate().skip(before.len()) {
+                let path = format!("{}/{}", path, index);
+                changes.push(Change::Added { path, value: new.clone() });  // b 多出的元素
+            }
+        }
+        _ if a != b => changes.push(Change::Replaced { path: path.clone(), before: a.clone(), after: b.clone() }),
+        _ => {}  // 相同的标量
+    }
+}
@@ -0,0 +1,98 @@
+//! 识别输入的编码
+//!
+//! RFC 8259 要求在网络上交换的 JSON 使用 UTF-8，
+//! 但早先的 RFC 4627 也允许 UTF-16 和 UTF-32，一些系统至今仍会输出它们。
+//! 这里按开头的字节顺序标记或空字节的分布判断编码，转换为 UTF-8 之后再解析。
+
+use serde_json::Value;
+
+use crate::{ErrorKind, ParseError, parse, parse_bytes};
+
+/// 输入可能使用的编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+/// 根据开头的字节判断编码
+///
+/// 详细解释：
+/// 1. 有字节顺序标记时按标记判断：
+///    - EF BB BF：UTF-8
+///    - 00 00 FE FF：UTF-32BE；FF FE 00 00：UTF-32LE
+///    - FE FF：UTF-16BE；FF FE：UTF-16LE
+/// 2. 没有标记时，JSON 文本的前两个字符都是 ASCII，按空字节的位置判断：
+///    - 00 00 00 xx：UTF-32BE；xx 00 00 00：UTF-32LE
+///    - 00 xx：UTF-16BE；xx 00：UTF-16LE
+/// 3. 其余情况都按 UTF-8 处理
+///
+/// UTF-32LE 的标记以 FF FE 开头，所以必须先于 UTF-16LE 检查
+fn detect(bytes: &[u8]) -> Encoding {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Encoding::Utf8,
+        [0x00, 0x00, 0xFE, 0xFF, ..] => Encoding::Utf32Be,
+        [0xFF, 0xFE, 0x00, 0x00, ..] => Encoding::Utf32Le,
+        [0xFE, 0xFF, ..] => Encoding::Utf16Be,
+        [0xFF, 0xFE, ..] => Encoding::Utf16Le,
+        [0x00, 0x00, 0x00, b, ..] if *b != 0 => Encoding::Utf32Be,
+        [b, 0x00, 0x00, 0x00, ..] if *b != 0 => Encoding::Utf32Le,
+        [0x00, b, ..] if *b != 0 => Encoding::Utf16Be,
+        [b, 0x00, ..] if *b != 0 => Encoding::Utf16Le,
+        _ => Encoding::Utf8,
+    }
+}
+
+/// 自动识别编码并解析字节形式的 JSON 文本
+///
+/// 支持 UTF-8、UTF-16LE、UTF-16BE、UTF-32LE 和 UTF-32BE，识别规则见 detect。
+/// UTF-8 的输入与 parse_bytes 完全相同，不会复制；
+/// 其他编码先转换为 UTF-8 的 String 再解析，字节顺序标记会在解析时被跳过。
+///
+/// 错误处理：
+/// - UTF-8 输入不合法：返回 InvalidUtf8，与 parse_bytes 相同
+/// - UTF-16 或 UTF-32 输入的长度不是 2 或 4 的倍数、含有不成对的代理项或无效的码点：
+///   返回 InvalidEncoding
+/// - 转换之后的语法错误与 parse 相同
+///
+/// 对于 UTF-16 和 UTF-32 输入，错误位置都按转换后的 UTF-8 文本计算，
+/// 字节偏移量不是原始输入中的偏移量，行号和列号不受影响。
+///
+/// 举例：
+/// - 输入 5B 31 5D（"[1]"）-> UTF-8，成功
+/// - 输入 5B 00 31 00 5D 00 -> 按空字节判断为 UTF-16LE，成功
+/// - 输入 FE FF 00 5B 00 31 00 5D -> 按字节顺序标记判断为 UTF-16BE，成功
+/// - 输入 5B 00 31 -> 失败，UTF-16 输入的长度是奇数
+pub fn parse_auto(bytes: &[u8]) -> Result<Value, ParseError> {
+    let (width, decode): (usize, fn(&[u8]) -> u32) = match detect(bytes) {
+        Encoding::Utf8 => return parse_bytes(bytes),
+        Encoding::Utf16Le => (2, |b| u32::from(u16::from_le_bytes([b[0], b[1]]))),
+        Encoding::Utf16Be => (2, |b| u32::from(u16::from_be_bytes([b[0], b[1]]))),
+        Encoding::Utf32Le => (4, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        Encoding::Utf32Be => (4, |b| u32::from_be_bytes([b[0], b[1], b[2], b[3]])),
+    };
+    let (body, tail) = bytes.split_at(bytes.len() - bytes.len() % width);  // tail 是末尾不完整的码元
+    let units = body.chunks_exact(width).map(decode);
+    let mut text = String::with_capacity(body.len() / width);
+    if width == 2 {
+        for c in char::decode_utf16(units.map(|u| u as u16)) {
+            text.push(c.map_err(|_| invalid_encoding(&text))?);  // 不成对的代理项
+        }
+    } else {
+        for u in units {
+            text.push(char::from_u32(u).ok_or_else(|| invalid_encoding(&text))?);  // 超出范围的码点或代理项
+        }
+    }
+    if !tail.is_empty() {
+        return Err(invalid_encoding(&text));  // 长度不是码元宽度的倍数
+    }
+    parse(&text)
+}
+
+/// 构造编码错误，出错位置紧跟在已经成功转换的内容之后
+fn invalid_encoding(decoded: &str) -> ParseError {
+    ParseError::new(decoded, "", ErrorKind::InvalidEncoding)
+}
@@ -0,0 +1,55 @@
+//! 非递归的相等比较
+//!
+//! serde_json::Value 派生的 PartialEq 是递归的，比较两个极深的值时可能耗尽调用栈，
+//! 即使它们是用不受嵌套层数限制的 parse_iterative 解析出来的。
+//! 这里用堆上的显式栈逐层比较，嵌套层数只受内存限制。
+
+use serde_json::Value;
+
+/// 判断 a 和 b 是否相等，结果与 a == b 相同
+///
+/// 详细解释：
+/// 1. 栈里保存待比较的一对对值，开始时只有 (a, b)
+/// 2. 每次取出一对：类型不同或标量不相等时立即返回 false；
+///    数组要求长度相同，再把对应位置的元素成对压栈；
+///    对象要求成员个数相同，再按 a 的每个键在 b 中查找，找不到返回 false，找到则成对压栈
+/// 3. 栈空时所有位置都比较完，返回 true
+///
+/// 对象按键查找而不是按顺序比较，所以成员的顺序不影响结果，与 serde_json 的 Map 一致。
+///
+/// 举例：
+/// - values_equal({"a": [1, 2]}, {"a": [1, 2]}) -> true
+/// - values_equal([1, 2], [2, 1]) -> false
+/// - values_equal(1, 1.0) -> false，与 serde_json 相同，整数和浮点数不相等
+/// - 两个 100000 层嵌套的数组 -> true，不会栈溢出
+pub fn values_equal(a: &Value, b: &Value) -> bool {
+    let mut stack = vec![(a, b)];
+    while let Some(pair) = stack.pop() {
+        match pair {
+            (Value::Array(x), Value::Array(y)) => {
+                if x.len() != y.len() {
+                    return false;
+                }
+                stack.extend(x.iter().zip(y));  // 对应位置的元素
+            }
+            (Value::Object(x), Value::Object(y)) => {
+                if x.len() != y.len() {
+                    return false;
+                }
+                for (key, value) in x {
+                    match y.get(key) {
+                        Some(other) => stack.push((value, other)),
+                        None => return false,  // b 中没有这个键
+                    }
+                }
+            }
+            (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => return false,  // 类型不同
+            (x, y) => {
+                if x != y {
+                    return false;  // 两个标量，直接比较
+                }
+            }
+        }
+    }
+    true
+}
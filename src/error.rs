@@ -0,0 +1,319 @@
+//! 错误类型
+//!
+//! 对外只暴露 ParseError 和 ErrorKind，调用方不需要了解 nom 的错误类型。
+//! 解析器内部使用 InputError，它记录出错处剩余的输入，
+//! 在入口函数里再换算成行号和列号。
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// 解析错误的类别
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 输入为空，或者只有空白（允许注释时也包括只有注释）
+    ///
+    /// 与 UnexpectedEof 区分开，调用方可以把空输入当作特殊情况处理，例如当作 null
+    EmptyInput,
+    /// 输入在 JSON 值完整之前就结束了
+    UnexpectedEof,
+    /// 遇到了不符合 JSON 语法的字符
+    UnexpectedChar,
+    /// 字符串到输入末尾都没有结束引号，位置指向开始引号
+    UnterminatedString,
+    /// 数组到输入末尾都没有结束方括号，位置指向开始方括号
+    UnterminatedArray,
+    /// 对象到输入末尾都没有结束大括号，位置指向开始大括号
+    UnterminatedObject,
+    /// JSON 值之后还有多余的非空白内容，附带多余内容开头的一小段预览
+    ///
+    /// 多余内容在输入中的起始位置就是 ParseError::offset，
+    /// 可以用 &input[..offset] 取出合法的部分
+    TrailingData(String),
+    /// 数字格式无效，或者无法用 JSON 数字表示（如溢出为无穷大）
+    InvalidNumber,
+    /// 打开 reject_lossy_numbers 时，数字经过 f64 之后无法还原
+    LossyNumber,
+    /// 字符串中的转义序列无效
+    InvalidEscape,
+    /// 数组或对象的嵌套层数超过限制
+    DepthExceeded,
+    /// 字节输入不是合法的 UTF-8
+    InvalidUtf8,
+    /// 字节输入不是合法的 UTF-16 或 UTF-32
+    InvalidEncoding,
+    /// 对象中出现了重复的键，附带重复的键名
+    DuplicateKey(String),
+    /// 超过了解析配置中的资源限制，例如 max_elements
+    ResourceLimit,
+    /// 数字没有通过 Parser::with_number_validator 设置的校验，附带校验函数给出的原因
+    RejectedNumber(String),
+    /// 打开 require_root_container 时，顶层的值不是对象或数组，位置指向这个值
+    RootNotContainer,
+    /// JSON 语法正确，但无法转换为目标类型，附带 serde 给出的原因
+    Deserialize(String),
+    /// 从 Read 读取输入时发生 I/O 错误，附带错误信息
+    Io(String),
+}
+
+impl ErrorKind {
+    /// 返回该类别的简短描述
+    pub fn description(&self) -> &'static str {
+        match self {
+            ErrorKind::EmptyInput => "输入为空",
+            ErrorKind::UnexpectedEof => "输入意外结束",
+            ErrorKind::UnexpectedChar => "遇到意外的字符",
+            ErrorKind::UnterminatedString => "字符串缺少结束引号",
+            ErrorKind::UnterminatedArray => "数组缺少结束方括号",
+            ErrorKind::UnterminatedObject => "对象缺少结束大括号",
+            ErrorKind::TrailingData(_) => "JSON 值之后存在多余的数据",
+            ErrorKind::InvalidNumber => "无效的数字",
+            ErrorKind::LossyNumber => "数字经过 f64 之后无法还原",
+            ErrorKind::InvalidEscape => "无效的转义序列",
+            ErrorKind::DepthExceeded => "嵌套层数超过限制",
+            ErrorKind::InvalidUtf8 => "输入不是合法的 UTF-8",
+            ErrorKind::InvalidEncoding => "输入不是合法的 UTF-16 或 UTF-32",
+            ErrorKind::DuplicateKey(_) => "对象中存在重复的键",
+            ErrorKind::ResourceLimit => "超过资源限制",
+            ErrorKind::RejectedNumber(_) => "数字没有通过校验",
+            ErrorKind::RootNotContainer => "顶层的值不是对象或数组",
+            ErrorKind::Deserialize(_) => "无法转换为目标类型",
+            ErrorKind::Io(_) => "读取输入失败",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::TrailingData(preview) => write!(f, "{}：{:?}", self.description(), preview),  // 带上多余内容的预览
+            ErrorKind::DuplicateKey(key) => write!(f, "{}：{:?}", self.description(), key),  // 带上重复的键名
+            ErrorKind::RejectedNumber(reason) => write!(f, "{}：{}", self.description(), reason),  // 带上校验函数给出的原因
+            ErrorKind::Deserialize(reason) => write!(f, "{}：{}", self.description(), reason),  // 带上 serde 给出的原因
+            ErrorKind::Io(reason) => write!(f, "{}：{}", self.description(), reason),  // 带上 I/O 错误信息
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+/// 解析错误
+///
+/// 包含错误类别以及解析停止处的位置（从 1 开始的行号和列号，以及字节偏移量）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 错误类别
+    pub kind: ErrorKind,
+    /// 出错位置所在的行，从 1 开始
+    pub line: usize,
+    /// 出错位置所在的列，从 1 开始，按字符计数
+    pub column: usize,
+    /// 出错位置在输入中的字节偏移量，从 0 开始，可以直接用来切分输入
+    pub offset: usize,
+    /// 出错位置所在的那一行内容，不含换行符，Display 用它指出出错的列
+    ///
+    /// 行太长时只保留出错位置前后各 SNIPPET_CHARS 个字符，被截掉的部分用 "…" 代替。
+    /// 没有原文可以展示时（例如 Deserialize 错误）为空字符串。
+    pub source_line: String,
+    /// 错误是不是因为输入在值的中间结束，在末尾追加更多输入就可能成功
+    ///
+    /// 解析器用的是 nom 的 complete 解析器，截断的输入和真正不合法的输入都会报告为错误，
+    /// 这个字段把两者区分开：例如 "[1, 2"、"{\"a\": tr"、"1e" 和空输入为 true，
+    /// "[1 2"、"{1" 这样无论再追加什么都不会合法的输入为 false。
+    /// 只根据语法的形状判断，不考虑 max_depth 之类的限制；
+    /// StreamParser 用它决定是继续等待输入还是报告错误。
+    pub incomplete: bool,
+}
+
+/// source_line 在出错位置前后各保留的最多字符数
+const SNIPPET_CHARS: usize = 40;
+
+impl ParseError {
+    /// 根据原始输入和出错处剩余的输入构造错误
+    ///
+    /// nom 的错误只携带剩余输入，用原始长度减去剩余长度就得到字节偏移量，
+    /// 再由偏移量算出行号和列号
+    pub(crate) fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - remaining.len();  // 出错处的字节偏移量
+        let consumed = &input[..offset];  // 出错位置之前的内容
+        let consumed = consumed.strip_prefix('\u{FEFF}').unwrap_or(consumed);  // 字节顺序标记不占列
+        let line = consumed.matches('\n').count() + 1;  // 之前出现过几个换行符
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);  // 当前行的起始偏移量
+        let column = consumed[line_start..].chars().count() + 1;  // 当前行内的字符数
+        let source_line = snippet(&consumed[line_start..], remaining);
+        ParseError { kind, line, column, offset, source_line, incomplete: false }
+    }
+
+    /// 根据字节输入和 UTF-8 校验错误构造 InvalidUtf8 错误
+    /// 出错位置紧跟在合法的前缀之后，也就是第一个非法字节处
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn invalid_utf8(input: &[u8], err: core::str::Utf8Error) -> Self {
+        let valid = core::str::from_utf8(&input[..err.valid_up_to()]).unwrap_or_default();  // 合法的前缀
+        ParseError::new(valid, "", ErrorKind::InvalidUtf8)
+    }
+
+    /// 将解析器内部的 nom 错误转换为 ParseError，同时判断是不是输入被截断了
+    pub(crate) fn from_nom(input: &str, err: nom::Err<InputError<'_>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let before = &input[..input.len() - e.input.len()];
+                let incomplete = is_truncated(before, &e);
+                ParseError { incomplete, ..ParseError::new(input, e.input, e.kind) }
+            }
+            nom::Err::Incomplete(_) => ParseError { incomplete: true, ..ParseError::new(input, "", ErrorKind::UnexpectedEof) },
+        }
+    }
+}
+
+/// 截取出错位置所在的一行
+///
+/// before 是这一行中出错位置之前的内容，remaining 是从出错位置开始的剩余输入。
+/// 前后各最多保留 SNIPPET_CHARS 个字符，截掉的一侧加上 "…"
+fn snippet(before: &str, remaining: &str) -> String {
+    let after = remaining.split('\n').next().unwrap_or_default();  // 出错位置到行尾
+    let after = after.strip_suffix('\r').unwrap_or(after);  // CRLF 换行的行尾
+    let mut out = String::new();
+    let skip = before.chars().count().saturating_sub(SNIPPET_CHARS);
+    if skip > 0 {
+        out.push('…');  // 前面截掉了一部分
+    }
+    out.extend(before.chars().skip(skip));
+    out.extend(after.chars().take(SNIPPET_CHARS));
+    if after.chars().nth(SNIPPET_CHARS).is_some() {
+        out.push('…');  // 后面截掉了一部分
+    }
+    out
+}
+
+/// 输出错误信息，随后像编译器的诊断信息一样展示出错的那一行，并在出错的列下面标出 ^
+///
+/// 举例：第 3 行是缩进 4 个空格的 "age": 3O, 时，在字母 O 处出错，输出
+///
+/// ```text
+/// 遇到意外的字符，位于第 3 行第 13 列
+///   |
+/// 3 |     "age": 3O,
+///   |             ^
+/// ```
+///
+/// source_line 为空时只输出第一行的错误信息。
+/// 列号按字符计数，制表符原样保留以便对齐；全角字符占两列宽，^ 可能会略微偏左。
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}，位于第 {} 行第 {} 列", self.kind, self.line, self.column)?;
+        if self.source_line.is_empty() {
+            return Ok(());
+        }
+        let before = self.column - 1;  // 出错位置之前的字符数
+        let caret = if before > SNIPPET_CHARS { SNIPPET_CHARS + 1 } else { before };  // 截掉时开头多了一个 "…"
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());  // 行号栏的宽度
+        write!(f, "\n{} |\n{} | {}\n{} | ", gutter, number, self.source_line, gutter)?;
+        for c in self.source_line.chars().take(caret) {
+            f.write_str(if c == '\t' { "\t" } else { " " })?;  // 制表符原样保留，其余字符换成空格
+        }
+        f.write_str("^")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// 解析器内部使用的 nom 错误类型
+///
+/// 记录出错处剩余的输入和错误类别。
+/// nom 自带的错误（如 tag、char 匹配失败）统一归为 UnexpectedChar，
+/// 如果剩余输入已经为空则归为 UnexpectedEof。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InputError<'a> {
+    /// 出错处剩余的输入
+    pub(crate) input: &'a str,
+    /// 错误类别
+    pub(crate) kind: ErrorKind,
+}
+
+impl<'a> InputError<'a> {
+    /// 构造指定类别的错误
+    pub(crate) fn new(input: &'a str, kind: ErrorKind) -> Self {
+        InputError { input, kind }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for InputError<'a> {
+    fn from_error_kind(input: &'a str, kind: nom::error::ErrorKind) -> Self {
+        let kind = if kind == nom::error::ErrorKind::Eof {
+            ErrorKind::TrailingData(preview(input))  // all_consuming 发现值后面还有内容
+        } else if input.is_empty() {
+            ErrorKind::UnexpectedEof  // 没有剩余输入可供匹配
+        } else {
+            ErrorKind::UnexpectedChar
+        };
+        InputError { input, kind }
+    }
+
+    fn append(_: &'a str, _: nom::error::ErrorKind, other: Self) -> Self {
+        other  // 保留最内层的错误，它的位置最准确
+    }
+}
+
+/// 预览中最多保留的字符数
+const PREVIEW_CHARS: usize = 16;
+
+/// 截取多余内容开头的一小段，超出 PREVIEW_CHARS 个字符时以 "…" 结尾
+///
+/// 举例：
+/// - "garbage" -> "garbage"
+/// - "a very long piece of trailing text" -> "a very long piec…"
+fn preview(rest: &str) -> String {
+    match rest.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => {
+            let mut out = String::from(&rest[..end]);
+            out.push('…');
+            out
+        }
+        None => String::from(rest),
+    }
+}
+
+/// 判断解析失败是不是因为输入在值的中间结束，再多一些输入就可能成功
+///
+/// before 是出错位置之前的输入。
+/// EmptyInput、UnexpectedEof 和几种 Unterminated 错误本来就只在输入用完时产生；
+/// UnexpectedChar 和 InvalidNumber 要看出错位置之后是不是一个被截断的记号
+fn is_truncated(before: &str, err: &InputError<'_>) -> bool {
+    match err.kind {
+        ErrorKind::EmptyInput
+        | ErrorKind::UnexpectedEof
+        | ErrorKind::UnterminatedString
+        | ErrorKind::UnterminatedArray
+        | ErrorKind::UnterminatedObject => true,
+        ErrorKind::UnexpectedChar | ErrorKind::InvalidNumber => is_truncated_token(before, err.input),
+        _ => false,
+    }
+}
+
+/// 判断 rest 是不是一个被截断的字面量或数字，例如 "tr"、"-"、"1e+"
+///
+/// 被截断的记号必须位于期待一个值的地方，也就是输入开头或者 '['、':'、',' 之后，
+/// 否则 "[1 2" 里的 "2" 也会被当作没有结束的数字。
+/// 数组元素解析失败时会退回到逗号之前，所以先跳过开头的逗号和空白，
+/// 这个逗号只能跟在一个值之后，"[," 和 "[1,," 不算截断。
+/// allow_nan 的字面量也算在内，严格模式下它们之后总会报告别的错误
+fn is_truncated_token(before: &str, rest: &str) -> bool {
+    const WHITESPACE: [char; 4] = [' ', '\t', '\r', '\n'];
+    let trimmed = rest.trim_start_matches(WHITESPACE);
+    let after_entry = !before.trim_end_matches(WHITESPACE).ends_with(['[', '{', ',', ':']);  // 前面是一个完整的值
+    let (before, rest) = match trimmed.strip_prefix(',') {
+        Some(after) if after_entry => (",", after.trim_start_matches(WHITESPACE)),  // 最多跳过一个逗号
+        _ => (before, trimmed),
+    };
+    if rest.is_empty() {
+        return true;
+    }
+    let before = before.strip_prefix('\u{FEFF}').unwrap_or(before);  // 字节顺序标记不算内容
+    if !matches!(before.trim_end_matches(WHITESPACE).chars().next_back(), None | Some('[' | ':' | ',')) {
+        return false;  // 这里期待的是逗号、冒号或结束括号，不是值
+    }
+    ["true", "false", "null", "NaN", "Infinity", "-Infinity"]
+        .iter()
+        .any(|lit| lit.len() > rest.len() && lit.starts_with(rest))
+        || rest.chars().all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+}
@@ -0,0 +1,143 @@
+//! 展开嵌套结构
+//!
+//! 把嵌套的 Value 展开为一层的键值对，键是用分隔符连接起来的路径，
+//! 例如 {"address": {"city": "x"}} 展开为 {"address.city": "x"}，
+//! 适合导出到只接受扁平键值对的环境（环境变量、配置表格等）。
+//! unflatten 把展开后的结果还原为嵌套结构。
+
+use serde_json::{Map, Value};
+
+use crate::pointer::parse_index;
+
+/// 默认的路径分隔符
+pub const DEFAULT_SEPARATOR: &str = ".";
+
+/// 用默认分隔符 "." 展开 value
+///
+/// 举例：
+/// - 输入 {"address": {"city": "x"}, "courses": ["a", "b"]}
+///   -> {"address.city": "x", "courses.0": "a", "courses.1": "b"}
+pub fn flatten(value: &Value) -> Map<String, Value> {
+    flatten_with_separator(value, DEFAULT_SEPARATOR)
+}
+
+/// 用指定的分隔符展开 value
+///
+/// 详细解释：
+/// 1. 对象的键和数组的下标依次作为路径的片段，用 separator 连接
+/// 2. 标量、空数组和空对象是展开的终点，原样作为值保存，
+///    这样空容器在还原时不会丢失
+/// 3. value 本身就是终点时，得到只有一个成员的结果，键是空字符串
+///
+/// 键本身含有分隔符，或者对象的键是数字或空字符串时，展开的结果无法准确还原，
+/// 需要时可以换一个不会出现在键里的分隔符。
+///
+/// 举例：
+/// - separator 为 "__" 时，输入 {"a": {"b": 1}} -> {"a__b": 1}
+/// - 输入 {"a": [], "b": {}} -> {"a": [], "b": {}}
+/// - 输入 5 -> {"": 5}
+pub fn flatten_with_separator(value: &Value, separator: &str) -> Map<String, Value> {
+    let mut out = Map::new();
+    let mut path = String::new();  // 当前位置的路径，随递归增减
+    flatten_into(&mut out, &mut path, value, separator);
+    out
+}
+
+/// 把 path 处的 value 展开后加入 out
+fn flatten_into(out: &mut Map<String, Value>, path: &mut String, value: &Value, separator: &str) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Array(items) if !items.is_empty() => items.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+        Value::Object(map) if !map.is_empty() => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        _ => {
+            out.insert(path.clone(), value.clone());  // 到达终点
+            return;
+        }
+    };
+    for (segment, child) in children {
+        let len = path.len();
+        if len > 0 {
+            path.push_str(separator);  // 顶层的片段前面不加分隔符
+        }
+        path.push_str(&segment);
+        flatten_into(out, path, child, separator);
+        path.truncate(len);  // 回到当前容器
+    }
+}
+
+/// 用默认分隔符 "." 还原展开的结果
+///
+/// 举例：
+/// - 输入 {"address.city": "x", "courses.0": "a"} -> {"address": {"city": "x"}, "courses": ["a"]}
+pub fn unflatten(map: &Map<String, Value>) -> Value {
+    unflatten_with_separator(map, DEFAULT_SEPARATOR)
+}
+
+/// 用指定的分隔符还原展开的结果
+///
+/// 详细解释：
+/// 1. 每个键按 separator 拆分成路径片段，从顶层开始逐层找到或创建容器
+/// 2. 片段是十进制下标（"0" 或者不以 0 开头的数字），并且恰好指向数组中已有的元素
+///    或者末尾之后的第一个位置时，作为数组下标；否则作为对象的键。
+///    所以数组只能从下标 0 开始依次增长，"a.4000000000" 这样的键不会分配巨大的数组
+/// 3. 数组遇到不能作为下标的片段时，改为对象，已有的元素以下标为键保留
+/// 4. 空字符串键表示整个文档
+/// 5. 路径经过的位置已经是标量时（例如同时有 "a" 和 "a.b"），该值被替换为所需的容器
+///
+/// 只要 v 中的键都不含分隔符、不是数字、也不是空字符串，就有 unflatten(&flatten(&v)) == v
+///
+/// 举例：
+/// - 输入 {"a.0": 1, "a.1": 2} -> {"a": [1, 2]}
+/// - 输入 {"a.1": true} -> {"a": {"1": true}}，数组必须从下标 0 开始
+/// - 输入 {"a.0": 1, "a.5": 2} -> {"a": {"0": 1, "5": 2}}
+/// - 输入 {"": 5} -> 5
+pub fn unflatten_with_separator(map: &Map<String, Value>, separator: &str) -> Value {
+    let mut root = Value::Null;
+    for (key, value) in map {
+        if key.is_empty() {
+            root = value.clone();  // 整个文档就是一个终点
+            continue;
+        }
+        let segments: Vec<&str> = key.split(separator).collect();
+        insert_path(&mut root, &segments, value.clone());
+    }
+    if root.is_null() && !map.contains_key("") {
+        return Value::Object(Map::new());  // 空的展开结果还原为空对象
+    }
+    root
+}
+
+/// 沿着 segments 找到或创建容器，把 value 放到路径末尾
+fn insert_path(target: &mut Value, segments: &[&str], value: Value) {
+    let Some((first, rest)) = segments.split_first() else {
+        *target = value;  // 到达路径末尾
+        return;
+    };
+    let index = parse_index(first).filter(|&i| match target {
+        Value::Array(items) => i <= items.len(),  // 已有的元素，或者追加到末尾
+        Value::Object(_) => false,  // 已经是对象，数字也只是键
+        _ => i == 0,  // 新的数组从下标 0 开始
+    });
+    let slot = match index {
+        Some(index) => {
+            if !target.is_array() {
+                *target = Value::Array(Vec::new());  // 下标片段需要数组
+            }
+            let Value::Array(items) = target else { return };
+            if index == items.len() {
+                items.push(Value::Null);
+            }
+            &mut items[index]
+        }
+        None => {
+            let map = match core::mem::take(target) {
+                Value::Object(map) => map,
+                Value::Array(items) => items.into_iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),  // 元素以下标为键保留
+                _ => Map::new(),  // 键片段需要对象
+            };
+            *target = Value::Object(map);
+            let Value::Object(map) = target else { return };
+            map.entry(first.to_string()).or_insert(Value::Null)
+        }
+    };
+    insert_path(slot, rest, value);
+}
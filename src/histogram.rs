@@ -0,0 +1,69 @@
+//! 值类型的统计
+//!
+//! 统计解析出的 Value 中每种类型各出现了多少次，
+//! 用于推断模式或者粗略了解一个文档的构成。
+
+use serde_json::Value;
+
+/// 每种类型的值出现的次数
+///
+/// 根节点、数组元素和对象成员的值都各算一次，对象的键不计入 strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TypeCounts {
+    /// null 的个数
+    pub nulls: usize,
+    /// 布尔值的个数
+    pub bools: usize,
+    /// 数字的个数
+    pub numbers: usize,
+    /// 字符串的个数
+    pub strings: usize,
+    /// 数组的个数
+    pub arrays: usize,
+    /// 对象的个数
+    pub objects: usize,
+}
+
+impl TypeCounts {
+    /// 所有类型加起来的个数，即文档中值的总数
+    ///
+    /// 与 ParserConfig::max_elements 的计数方式相同
+    pub fn total(&self) -> usize {
+        self.nulls + self.bools + self.numbers + self.strings + self.arrays + self.objects
+    }
+}
+
+/// 统计 value 中每种类型的值各有多少个
+///
+/// 举例：
+/// - 输入 1 -> numbers 为 1，其余为 0
+/// - 输入 [1, "a", null] -> arrays 1、numbers 1、strings 1、nulls 1
+/// - 输入 {"a": {"b": [true]}} -> objects 2、arrays 1、bools 1
+/// - tests/sample.json 中的示例文档 -> nulls 1、bools 2、numbers 11、strings 16、arrays 6、objects 11，共 47 个值
+pub fn type_histogram(value: &Value) -> TypeCounts {
+    let mut counts = TypeCounts::default();
+    count_into(&mut counts, value);
+    counts
+}
+
+/// 把 value 及其所有子节点计入 counts
+fn count_into(counts: &mut TypeCounts, value: &Value) {
+    match value {
+        Value::Null => counts.nulls += 1,
+        Value::Bool(_) => counts.bools += 1,
+        Value::Number(_) => counts.numbers += 1,
+        Value::String(_) => counts.strings += 1,
+        Value::Array(items) => {
+            counts.arrays += 1;
+            for item in items {
+                count_into(counts, item);
+            }
+        }
+        Value::Object(map) => {
+            counts.objects += 1;
+            for item in map.values() {
+                count_into(counts, item);
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! 非递归解析器
+//!
+//! parser 模块里的 parse_array 和 parse_object 通过递归处理嵌套结构，
+//! 嵌套层数受调用栈大小限制。这里用一个分配在堆上的显式栈
+//! 保存尚未完成的数组和对象，嵌套层数只受内存限制。
+//!
+//! 标量（null、布尔值、数字、字符串）直接复用 parser 模块里的解析器，
+//! 因此两种解析器得到的 Value 完全相同。
+//! 非递归解析器总是按默认配置（严格的 JSON 语法）解析。
+
+use nom::{
+    Parser,
+    branch::alt,
+    character::complete::{char as char_parser, multispace0},
+    combinator::{all_consuming, cut, map},
+    sequence::terminated,
+};
+use serde_json::{Map, Value};
+
+use crate::config::ParserConfig;
+use crate::parser::{PResult, parse_bool, parse_null, parse_number, parse_string, reject_empty, unterminated_container};
+
+/// 栈中尚未完成的容器
+enum Frame {
+    /// 正在收集元素的数组
+    Array(Vec<Value>),
+    /// 正在收集成员的对象，以及等待取值的键
+    Object(Map<String, Value>, String),
+}
+
+/// 解析标量值：null、布尔值、数字或字符串
+fn parse_scalar<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, Value> {
+    alt((
+        parse_null,
+        parse_bool,
+        |i| parse_number(i, config),
+        map(parse_string, |s| Value::String(s.into_owned())),
+    )).parse(input)
+}
+
+/// 解析对象的键和冒号
+/// 键必须是字符串，冒号前后可以有空白
+fn parse_key(input: &str) -> PResult<'_, String> {
+    let (input, key) = cut(terminated(parse_string, multispace0)).parse(input)?;  // 键（必须是字符串）
+    let (input, _) = cut(terminated(char_parser(':'), multispace0)).parse(input)?;  // 冒号分隔符
+    Ok((input, key.into_owned()))
+}
+
+/// 解析任意 JSON 值，不使用递归
+///
+/// 详细解释：
+/// 1. 主循环的每一轮都从“期待一个值”开始：
+///    - 遇到 '[' 或 '{'：把新容器压入栈，接着期待它的第一个元素或成员
+///    - 遇到空容器 "[]" 或 "{}"：直接得到一个完成的值
+///    - 否则按标量解析，得到一个完成的值
+/// 2. 得到完成的值之后，弹出栈顶的容器并把值放进去：
+///    - 栈为空：这就是最终结果
+///    - 容器之后是 ','：容器重新入栈，继续期待下一个元素或成员
+///    - 容器之后是 ']' 或 '}'：容器本身成为新的完成的值，继续交给下一层
+///
+/// 与 parse_primary 一样，值前后的空白都会被吃掉，
+/// 容器内部的失败用 cut 直接报告；
+/// 输入在容器结束之前用完时，与 parse_array 和 parse_object 一样
+/// 返回 UnterminatedArray 或 UnterminatedObject，位置指向最里面那个没有结束的容器
+pub(crate) fn parse_value(input: &str) -> PResult<'_, Value> {
+    let config = ParserConfig::default();
+    let mut opens = Vec::new();  // 尚未结束的容器的开括号位置，与栈中的容器一一对应
+    parse_containers(input, &config, &mut opens).map_err(|e| match opens.last() {
+        Some(open) => unterminated_container(open, e, &config),
+        None => e,
+    })
+}
+
+/// parse_value 的主循环，opens 记录尚未结束的容器从哪里开始
+fn parse_containers<'a>(input: &'a str, config: &ParserConfig, opens: &mut Vec<&'a str>) -> PResult<'a, Value> {
+    let mut stack: Vec<Frame> = Vec::new();  // 尚未完成的容器
+    let (mut input, _) = multispace0(input)?;  // 前导空白
+
+    'value: loop {
+        // 第一步：期待一个值
+        let mut value = if let Some(rest) = input.strip_prefix('[') {
+            opens.push(input);
+            let (rest, _) = multispace0(rest)?;
+            if let Some(rest) = rest.strip_prefix(']') {
+                opens.pop();
+                input = rest;
+                Value::Array(Vec::new())  // 空数组
+            } else {
+                stack.push(Frame::Array(Vec::new()));  // 新数组入栈，接着解析第一个元素
+                input = rest;
+                continue 'value;
+            }
+        } else if let Some(rest) = input.strip_prefix('{') {
+            opens.push(input);
+            let (rest, _) = multispace0(rest)?;
+            if let Some(rest) = rest.strip_prefix('}') {
+                opens.pop();
+                input = rest;
+                Value::Object(Map::new())  // 空对象
+            } else {
+                let (rest, key) = parse_key(rest)?;  // 第一个成员的键
+                stack.push(Frame::Object(Map::new(), key));  // 新对象入栈，接着解析第一个值
+                input = rest;
+                continue 'value;
+            }
+        } else {
+            let (rest, value) = if stack.is_empty() {
+                parse_scalar(input, config)?  // 顶层值失败时保持普通错误
+            } else {
+                cut(|i| parse_scalar(i, config)).parse(input)?  // 容器内部的失败直接报告
+            };
+            input = rest;
+            value
+        };
+        let (rest, _) = multispace0(input)?;  // 值之后的空白
+        input = rest;
+
+        // 第二步：把完成的值交给栈顶的容器
+        while let Some(frame) = stack.pop() {
+            match frame {
+                Frame::Array(mut items) => {
+                    items.push(value);
+                    let (rest, c) = cut(terminated(alt((char_parser(','), char_parser(']'))), multispace0))
+                        .parse(input)?;  // 逗号或结束方括号
+                    input = rest;
+                    if c == ',' {
+                        stack.push(Frame::Array(items));  // 还有下一个元素
+                        continue 'value;
+                    }
+                    opens.pop();
+                    value = Value::Array(items);  // 数组结束，交给下一层
+                }
+                Frame::Object(mut map, key) => {
+                    map.insert(key, value);
+                    let (rest, c) = cut(terminated(alt((char_parser(','), char_parser('}'))), multispace0))
+                        .parse(input)?;  // 逗号或结束大括号
+                    input = rest;
+                    if c == ',' {
+                        let (rest, key) = parse_key(input)?;  // 下一个成员的键
+                        stack.push(Frame::Object(map, key));  // 还有下一个成员
+                        input = rest;
+                        continue 'value;
+                    }
+                    opens.pop();
+                    value = Value::Object(map);  // 对象结束，交给下一层
+                }
+            }
+        }
+        return Ok((input, value));  // 栈为空，得到最终结果
+    }
+}
+
+/// 用非递归解析器解析完整的 JSON 文档
+/// 与 parser::parse_document 相同，空输入返回 EmptyInput，值后面不允许再有任何内容
+pub(crate) fn parse_document(input: &str) -> PResult<'_, Value> {
+    reject_empty(input, &ParserConfig::default())?;
+    all_consuming(parse_value).parse(input)
+}
@@ -0,0 +1,479 @@
+//! 不依赖 serde_json 的 JSON 值
+//!
+//! 只需要解析的用户不必为了一个 Value 类型引入整个 serde_json。
+//! 这里的 Value 只用到 alloc 中的 Vec、String、Rc 和 BTreeMap，
+//! 关闭默认的 serde_json 和 std 特性后，这个模块仍然可以使用。
+//!
+//! 举例：
+//! - json::parse("{\"a\": [1, 2]}") -> 成功，返回 json::Value::Object
+//! - value.get("a") -> 对象中键 "a" 对应的值
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+
+use crate::build::{FromJson, KeyCache};
+use crate::config::ParserConfig;
+use crate::{ParseError, parser};
+
+/// 对象的成员表，按键排序
+///
+/// 键是 Rc<str>，打开 intern_keys 解析时，各个对象里相同的键共享同一份内存。
+/// 查找时可以直接用 &str，例如 map.get("a")。
+pub type Map = BTreeMap<Rc<str>, Value>;
+
+/// JSON 数字
+///
+/// 整数尽量按整数保存，不会因为经过 f64 而丢失精度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// 非负整数
+    PosInt(u64),
+    /// 负整数
+    NegInt(i64),
+    /// 浮点数，一定是有限值
+    Float(f64),
+}
+
+impl Number {
+    /// 由已经通过语法检查的数字文本构造数字
+    ///
+    /// 与 serde_json 的转换规则相同：
+    /// 没有小数点和指数时先尝试整数，否则（或整数超出 u64 范围）按 f64 解析。
+    /// 结果是无穷大时返回 None
+    pub(crate) fn from_text(s: &str) -> Option<Number> {
+        if !s.contains(['.', 'e', 'E']) {
+            if let Ok(i) = s.parse::<i64>() {
+                return Some(match u64::try_from(i) {
+                    Ok(u) => Number::PosInt(u),  // 包括 "-0"
+                    Err(_) => Number::NegInt(i),
+                });
+            }
+            if let Ok(u) = s.parse::<u64>() {
+                return Some(Number::PosInt(u));
+            }
+        }
+        let f = s.parse::<f64>().ok()?;
+        f.is_finite().then_some(Number::Float(f))  // JSON 数字不能是无穷大
+    }
+
+    /// 转换为 f64，大整数可能丢失精度
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::PosInt(u) => u as f64,
+            Number::NegInt(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    /// 如果是可以用 i64 表示的整数则返回它
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::PosInt(u) => i64::try_from(u).ok(),
+            Number::NegInt(i) => Some(i),
+            Number::Float(_) => None,
+        }
+    }
+
+    /// 如果是可以用 u64 表示的整数则返回它
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Number::PosInt(u) => Some(u),
+            _ => None,
+        }
+    }
+
+    /// 是否为可以用 i64 表示的整数
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    /// 是否为可以用 u64 表示的整数
+    pub fn is_u64(&self) -> bool {
+        matches!(self, Number::PosInt(_))
+    }
+
+    /// 是否按浮点数保存，也就是原文带有小数点或指数，或者超出了整数的范围
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Number::Float(_))
+    }
+
+    /// 如果数值恰好是一个 i64 整数则返回它
+    ///
+    /// 与 as_i64 不同，小数部分为 0 的浮点数也算整数，例如 3.0 和 1e2；
+    /// 有小数部分或者超出 i64 范围时返回 None
+    ///
+    /// 举例：
+    /// - 3、3.0、3e0 -> Some(3)
+    /// - 3.5 -> None
+    /// - 1e19 -> None，超出 i64 范围
+    pub fn as_exact_i64(&self) -> Option<i64> {
+        match *self {
+            Number::Float(f) => exact_i64(f),
+            _ => self.as_i64(),
+        }
+    }
+}
+
+/// 浮点数恰好是一个 i64 整数时返回它
+/// 不依赖 std 的 fract，而是转换为 i64 再转换回来比较
+pub(crate) fn exact_i64(f: f64) -> Option<i64> {
+    if !(-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&f) {
+        return None;  // 超出 i64 范围，as 转换会饱和
+    }
+    let i = f as i64;
+    (i as f64 == f).then_some(i)
+}
+
+/// JSON 值
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Value {
+    /// null
+    #[default]
+    Null,
+    /// 布尔值
+    Bool(bool),
+    /// 数字
+    Number(Number),
+    /// 字符串
+    String(String),
+    /// 数组
+    Array(Vec<Value>),
+    /// 对象
+    Object(Map),
+    /// 精确的十进制小数，只在打开 ParserConfig::decimal 时出现
+    #[cfg(feature = "rust_decimal")]
+    Decimal(Decimal),
+}
+
+impl Value {
+    /// 是否为 null
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// 如果是布尔值则返回它
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// 如果是数字则返回它
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Value::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// 如果是数字则转换为 f64
+    pub fn as_f64(&self) -> Option<f64> {
+        #[cfg(feature = "rust_decimal")]
+        if let Value::Decimal(d) = self {
+            return d.to_f64();  // 可能丢失精度
+        }
+        self.as_number().map(Number::as_f64)
+    }
+
+    /// 如果是 Decimal 则返回它
+    #[cfg(feature = "rust_decimal")]
+    pub fn as_decimal(&self) -> Option<&Decimal> {
+        match self {
+            Value::Decimal(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// 如果是可以用 i64 表示的整数则返回它
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number().and_then(Number::as_i64)
+    }
+
+    /// 如果是可以用 u64 表示的整数则返回它
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number().and_then(Number::as_u64)
+    }
+
+    /// 是否为可以用 i64 表示的整数，见 Number::is_i64
+    pub fn is_i64(&self) -> bool {
+        self.as_number().is_some_and(Number::is_i64)
+    }
+
+    /// 是否为可以用 u64 表示的整数，见 Number::is_u64
+    pub fn is_u64(&self) -> bool {
+        self.as_number().is_some_and(Number::is_u64)
+    }
+
+    /// 是否为按浮点数保存的数字，见 Number::is_f64；Decimal 不算
+    pub fn is_f64(&self) -> bool {
+        self.as_number().is_some_and(Number::is_f64)
+    }
+
+    /// 如果数值恰好是一个 i64 整数则返回它，见 Number::as_exact_i64
+    /// Decimal 没有小数部分并且在 i64 范围内时同样返回它
+    pub fn as_exact_i64(&self) -> Option<i64> {
+        #[cfg(feature = "rust_decimal")]
+        if let Value::Decimal(d) = self {
+            return if d.is_integer() { d.to_i64() } else { None };
+        }
+        self.as_number().and_then(Number::as_exact_i64)
+    }
+
+    /// 如果是字符串则返回其内容
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 如果是数组则返回其元素
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 如果是对象则返回其成员表
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// 取对象中 key 对应的值，不是对象或没有这个键时返回 None
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object().and_then(|map| map.get(key))
+    }
+}
+
+/// 非递归地释放嵌套的数组和对象
+///
+/// 派生的析构会逐层递归，释放极深的值时可能耗尽调用栈。
+/// 这里先把子节点都移到堆上的显式栈里，再逐个取出：
+/// 每个子节点在释放之前，它自己的子节点也被移进栈中，所以每次真正释放的都是已经清空的容器或标量。
+/// 嵌套层数再深，调用栈的深度也不会增加。
+impl Drop for Value {
+    fn drop(&mut self) {
+        let mut stack = match self {
+            Value::Array(items) if items.iter().any(is_container) => core::mem::take(items),
+            Value::Object(map) if map.values().any(is_container) => core::mem::take(map).into_values().collect(),
+            _ => return,  // 没有嵌套的容器，派生的析构不会递归
+        };
+        while let Some(mut value) = stack.pop() {
+            match &mut value {
+                Value::Array(items) => stack.append(items),
+                Value::Object(map) => stack.extend(core::mem::take(map).into_values()),
+                _ => {}
+            }
+        }  // value 在这里释放，此时它已经没有子节点
+    }
+}
+
+/// 是否为数组或对象
+fn is_container(value: &Value) -> bool {
+    matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+/// 输出 JSON 数字
+///
+/// 整数原样输出，浮点数总是带有小数点或指数（例如 1.0、1e300），
+/// 重新解析后仍然是浮点数。
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Number::PosInt(u) => write!(f, "{}", u),
+            Number::NegInt(i) => write!(f, "{}", i),
+            Number::Float(x) => write!(f, "{:?}", x),  // Debug 格式总是保留小数点，很大或很小时使用指数
+        }
+    }
+}
+
+/// 输出紧凑的 JSON 文本，不含多余的空白，与顶层的 to_string 格式相同
+///
+/// 字符串中的引号、反斜杠和控制字符按 JSON 规则转义，其他字符原样输出；对象成员按键排序输出。
+/// 与 FromStr 互为逆操作：输出的文本再解析得到相等的 Value。
+///
+/// 举例：
+///
+/// ```
+/// use json_parser::json::Value;
+///
+/// let value: Value = "{\"b\": [1, 2.5, null], \"a\": \"x\\ny\"}".parse().unwrap();
+/// assert_eq!(value.to_string(), "{\"a\":\"x\\ny\",\"b\":[1,2.5,null]}");
+/// assert_eq!(value.to_string().parse::<Value>().unwrap(), value);
+/// ```
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write_escaped(f, s),
+            Value::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;  // 元素之间的逗号
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(']')
+            }
+            Value::Object(map) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;  // 成员之间的逗号
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(d) => write!(f, "{}", d),  // 按原来的精度输出，例如 1.50
+        }
+    }
+}
+
+/// 用 json::parse 解析，"[1,2,3]".parse::<Value>() 与 json::parse("[1,2,3]") 相同
+///
+/// 举例：
+///
+/// ```
+/// use json_parser::json::{Number, Value};
+///
+/// let value: Value = "[1, 2, 3]".parse().unwrap();
+/// assert_eq!(value.as_array().map(Vec::len), Some(3));
+/// assert_eq!(value.as_array().unwrap()[0], Value::Number(Number::PosInt(1)));
+///
+/// let err = "[1, 2".parse::<Value>().unwrap_err();
+/// assert_eq!(err.offset, 0);  // ParseError 与 json::parse 返回的相同
+/// ```
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+/// 写出带引号的字符串，按 JSON 规则转义
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;  // 开始引号
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,        // 引号
+            '\\' => f.write_str("\\\\")?,       // 反斜杠
+            '\n' => f.write_str("\\n")?,        // 换行
+            '\r' => f.write_str("\\r")?,        // 回车
+            '\t' => f.write_str("\\t")?,        // 制表符
+            '\u{0008}' => f.write_str("\\b")?,  // 退格
+            '\u{000C}' => f.write_str("\\f")?,  // 换页
+            c if c < '\u{0020}' => write!(f, "\\u{:04x}", c as u32)?,  // 其他控制字符
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')  // 结束引号
+}
+
+impl<'a> FromJson<'a> for Value {
+    type Map = Map;
+
+    fn null() -> Self {
+        Value::Null
+    }
+
+    fn bool(b: bool) -> Self {
+        Value::Bool(b)
+    }
+
+    fn number(text: &'a str, config: &ParserConfig) -> Option<Self> {
+        #[cfg(feature = "rust_decimal")]
+        if config.decimal && text.contains(['.', 'e', 'E'])
+            && let Some(d) = decimal_from_text(text)
+        {
+            return Some(Value::Decimal(d));
+        }
+        #[cfg(not(feature = "rust_decimal"))]
+        let _ = config;  // 未启用 rust_decimal 特性时没有用到配置
+        Number::from_text(text).map(Value::Number)  // 不支持 arbitrary_precision，总是转换为数字
+    }
+
+    fn string(s: Cow<'a, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        Value::Array(items)
+    }
+
+    fn new_map() -> Self::Map {
+        Map::new()
+    }
+
+    fn map_contains(map: &Self::Map, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn map_insert(map: &mut Self::Map, key: Cow<'a, str>, value: Self) {
+        map.insert(Rc::from(key.as_ref()), value);
+    }
+
+    fn map_insert_interned(map: &mut Self::Map, key: Cow<'a, str>, value: Self, keys: &KeyCache) {
+        map.insert(keys.intern(&key), value);
+    }
+
+    fn object(map: Self::Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+/// 把已经通过语法检查的小数文本精确转换为 Decimal
+///
+/// 有效数字超过 Decimal 的精度或者指数太大时返回 None，不会舍入
+///
+/// 举例：
+/// - "1000.50" -> 1000.50，保留末尾的 0
+/// - "1.5e-3" -> 0.0015
+/// - "0.12345678901234567890123456789" -> None，超过 28 位
+#[cfg(feature = "rust_decimal")]
+fn decimal_from_text(text: &str) -> Option<Decimal> {
+    match text.split_once(['e', 'E']) {
+        Some((base, _)) => {
+            Decimal::from_str_exact(base).ok()?;  // from_scientific 会把过长的底数舍入，这里先检查
+            Decimal::from_scientific(text).ok()
+        }
+        None => Decimal::from_str_exact(text).ok(),
+    }
+}
+
+/// 解析 JSON 文本，返回本模块的 Value
+///
+/// 语法规则与顶层的 parse 完全相同，只是结果类型不同
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    parse_with_config(input, &ParserConfig::default())
+}
+
+/// 按指定配置解析 JSON 文本，返回本模块的 Value
+///
+/// 数字转换为 Number（打开 decimal 时小数转换为 Decimal），
+/// arbitrary_precision 配置对这里的 Value 不起作用
+pub fn parse_with_config(input: &str, config: &ParserConfig) -> Result<Value, ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 跳过开头的字节顺序标记
+    parser::parse_document(text, config)
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
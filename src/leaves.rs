@@ -0,0 +1,56 @@
+//! 叶子节点及其路径
+//!
+//! 把文档中的每个标量和它的 JSON Pointer 路径配成一对，
+//! 例如 {"address": {"city": "x"}} 得到 ("/address/city", "x")。
+//! 结果可以逐行输出后用 grep 过滤，也可以作为类似 jq 的工具的基础。
+
+use serde_json::Value;
+
+use crate::pointer::escape_token;
+
+/// 返回 value 中所有标量及其 JSON Pointer 路径
+///
+/// 详细解释：
+/// 1. 按文档顺序深度优先遍历，数组按下标顺序，对象按成员表的顺序
+/// 2. 只产出标量（null、布尔值、数字和字符串），空数组和空对象没有叶子，不会出现在结果里
+/// 3. 路径按 RFC 6901 转义，键中的 '~' 和 '/' 分别写成 "~0" 和 "~1"，
+///    得到的路径可以直接交给 pointer 函数取回同一个值
+/// 4. value 本身就是标量时，结果只有一项，路径是空字符串
+///
+/// 举例：
+/// - 输入 {"address": {"city": "New York"}, "courses": ["Math"]}
+///   -> [("/address/city", "New York"), ("/courses/0", "Math")]
+/// - 输入 {"a/b": 1} -> [("/a~1b", 1)]
+/// - 输入 {"a": [], "b": {}} -> []
+/// - 输入 5 -> [("", 5)]
+/// - tests/sample.json 中的示例文档 -> 30 个叶子，其中有 ("/address/city", "New York")
+pub fn leaves(value: &Value) -> Vec<(String, &Value)> {
+    let mut out = Vec::new();
+    let mut path = String::new();  // 当前位置的路径，随递归增减
+    collect_leaves(&mut out, &mut path, value);
+    out
+}
+
+/// 把 path 处的 value 中的叶子加入 out
+fn collect_leaves<'v>(out: &mut Vec<(String, &'v Value)>, path: &mut String, value: &'v Value) {
+    let len = path.len();
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                path.push('/');
+                path.push_str(&index.to_string());
+                collect_leaves(out, path, item);
+                path.truncate(len);  // 回到当前容器
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                path.push('/');
+                path.push_str(&escape_token(key));
+                collect_leaves(out, path, item);
+                path.truncate(len);  // 回到当前容器
+            }
+        }
+        _ => out.push((path.clone(), value)),  // 标量就是叶子
+    }
+}
@@ -0,0 +1,156 @@
+//! 词法分析
+//!
+//! 有些工具只需要记号流而不需要完整的值树，例如语法高亮和重新排版。
+//! Lexer 把输入切分为记号，每个记号都带有它在输入中的字节范围。
+//! 字符串和数字的识别直接复用 parser 模块里的解析器，规则与 parse 完全相同。
+//!
+//! Lexer 只检查每个记号本身是否合法，不检查记号之间的顺序，
+//! 例如 "[1 2" 会依次产出 '['、1、2 三个记号而不会报错。
+
+use alloc::borrow::Cow;
+
+use crate::config::ParserConfig;
+use crate::error::ParseError;
+use crate::parser::{PResult, parse_bool, parse_null, parse_number, parse_string};
+
+/// 输入中的一段字节范围，start 包含在内，end 不包含在内
+///
+/// 范围只覆盖值或记号本身，不包括前后的空白和注释
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    /// 起始字节偏移量
+    pub start: usize,
+    /// 结束字节偏移量（不包含）
+    pub end: usize,
+}
+
+/// 计算 rest 在 source 中的字节偏移量
+fn offset(source: &str, rest: &str) -> usize {
+    source.len() - rest.len()
+}
+
+/// 计算 input 中被消耗掉的那段文本的范围，去掉末尾的空白
+///
+/// 标量解析器会顺带吃掉值之后的空白，而任何 JSON 值都不会以空白结尾，
+/// 所以去掉末尾空白之后剩下的恰好是值本身
+pub(crate) fn span_of(source: &str, input: &str, rest: &str) -> Span {
+    let start = offset(source, input);
+    let consumed = &input[..input.len() - rest.len()];
+    let end = start + consumed.trim_end_matches([' ', '\t', '\r', '\n']).len();
+    Span { start, end }
+}
+
+/// 记号的类别
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind<'a> {
+    /// 开始大括号 '{'
+    BraceOpen,
+    /// 结束大括号 '}'
+    BraceClose,
+    /// 开始方括号 '['
+    BracketOpen,
+    /// 结束方括号 ']'
+    BracketClose,
+    /// 冒号 ':'
+    Colon,
+    /// 逗号 ','
+    Comma,
+    /// 字符串，内容已经解码转义，不含转义时借用输入
+    String(Cow<'a, str>),
+    /// 数字在输入中的原文
+    Number(&'a str),
+    /// 布尔值
+    Bool(bool),
+    /// null
+    Null,
+}
+
+/// 一个记号及其在输入中的范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// 记号的类别
+    pub kind: TokenKind<'a>,
+    /// 记号在输入中的范围，字符串的范围包括两端的引号
+    pub span: Span,
+}
+
+/// 把 JSON 文本切分为记号的迭代器
+///
+/// 每次产出一个记号，遇到无法识别的内容时产出一个错误，之后不再产出任何内容。
+///
+/// 举例：输入 "{\"a\": [1, true]}" 依次产出
+/// BraceOpen、String("a")、Colon、BracketOpen、Number("1")、Comma、Bool(true)、BracketClose、BraceClose
+pub struct Lexer<'a> {
+    /// 完整的原始输入，用来换算偏移量和出错位置
+    source: &'a str,
+    /// 尚未处理的输入
+    rest: &'a str,
+    /// 是否已经出错，出错之后迭代结束
+    failed: bool,
+}
+
+impl<'a> Lexer<'a> {
+    /// 创建处理 input 的词法分析器
+    /// 开头的字节顺序标记会被跳过，但偏移量仍然按原始输入计算
+    pub fn new(input: &'a str) -> Self {
+        let rest = input.strip_prefix('\u{FEFF}').unwrap_or(input);
+        Lexer { source: input, rest, failed: false }
+    }
+
+    /// 识别 input 开头的一个记号，input 的开头不是空白
+    fn token(input: &'a str) -> PResult<'a, TokenKind<'a>> {
+        let config = ParserConfig::default();
+        let punct = match input.chars().next() {
+            Some('{') => Some(TokenKind::BraceOpen),
+            Some('}') => Some(TokenKind::BraceClose),
+            Some('[') => Some(TokenKind::BracketOpen),
+            Some(']') => Some(TokenKind::BracketClose),
+            Some(':') => Some(TokenKind::Colon),
+            Some(',') => Some(TokenKind::Comma),
+            _ => None,
+        };
+        if let Some(kind) = punct {
+            return Ok((&input[1..], kind));  // 标点都是单字节字符
+        }
+        if input.starts_with('"') {
+            let (rest, s) = parse_string(input)?;
+            return Ok((rest, TokenKind::String(s)));
+        }
+        if let Ok((rest, ())) = parse_null::<()>(input) {
+            return Ok((rest, TokenKind::Null));
+        }
+        if let Ok((rest, ())) = parse_bool::<()>(input) {
+            return Ok((rest, TokenKind::Bool(input.starts_with('t'))));
+        }
+        let (rest, ()) = parse_number::<()>(input, &config)?;
+        let len = input.len() - rest.len();
+        let text = input[..len].trim_end_matches([' ', '\t', '\r', '\n']);  // 数字解析器会吃掉后面的空白
+        Ok((&input[text.len()..], TokenKind::Number(text)))
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let input = self.rest.trim_start_matches([' ', '\t', '\r', '\n']);  // 记号之间的空白
+        if input.is_empty() {
+            self.rest = input;
+            return None;  // 输入结束
+        }
+        match Lexer::token(input) {
+            Ok((rest, kind)) => {
+                let span = span_of(self.source, input, rest);
+                self.rest = rest;
+                Some(Ok(Token { kind, span }))
+            }
+            Err(e) => {
+                self.failed = true;  // 出错之后不再继续
+                Some(Err(ParseError::from_nom(self.source, e)))
+            }
+        }
+    }
+}
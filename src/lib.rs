@@ -0,0 +1,329 @@
+//! 基于 nom 的 JSON 解析器
+//!
+//! 这个库把 JSON 文本解析为 serde_json::Value，也可以把 Value 重新输出为 JSON 文本。
+//! 对外只暴露 parse、to_string 等函数和 ParseError 错误类型，
+//! 需要调整配置时，使用 Parser 构建器，例如 Parser::new().allow_comments(true).parse(input)。
+//! 具体的解析器组合子都放在 parser 模块里，错误类型放在 error 模块里，
+//! 序列化放在 ser 模块里。
+//!
+//! 以 serde_json::Value 为结果的函数需要默认打开的 serde_json 特性。
+//! 关闭它之后，可以用 json 模块里不依赖 serde_json 的 Value 和 json::parse。
+//!
+//! 同时关闭默认的 std 特性后，这个库是 no_std 的，只需要 alloc，
+//! 可以用在嵌入式和 WASM 环境中。ParseError 对 std::error::Error 的实现需要 std 特性。
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "serde_json")]
+mod access;
+#[cfg(feature = "borrowed")]
+mod borrowed;
+mod build;
+mod builder;
+#[cfg(feature = "serde_json")]
+mod clone;
+mod config;
+#[cfg(feature = "serde_json")]
+mod de;
+#[cfg(feature = "serde_json")]
+mod depth;
+#[cfg(feature = "serde_json")]
+mod diff;
+#[cfg(feature = "serde_json")]
+mod encoding;
+#[cfg(feature = "serde_json")]
+mod equal;
+mod error;
+#[cfg(feature = "serde_json")]
+mod flatten;
+#[cfg(feature = "serde_json")]
+mod histogram;
+#[cfg(feature = "serde_json")]
+mod iterative;
+pub mod json;
+mod lexer;
+#[cfg(feature = "serde_json")]
+mod leaves;
+#[cfg(feature = "serde_json")]
+mod merge;
+mod minify;
+#[cfg(feature = "serde_json")]
+mod number;
+mod parser;
+#[cfg(feature = "serde_json")]
+mod patch;
+#[cfg(feature = "serde_json")]
+mod path;
+#[cfg(feature = "serde_json")]
+mod pointer;
+#[cfg(feature = "serde_json")]
+mod prune;
+#[cfg(feature = "serde_json")]
+mod reader;
+#[cfg(feature = "serde_json")]
+mod schema;
+#[cfg(feature = "serde_json")]
+mod ser;
+#[cfg(feature = "serde_json")]
+mod spanned;
+mod stats;
+#[cfg(feature = "serde_json")]
+mod stream;
+mod validate;
+mod visit;
+#[cfg(feature = "serde_json")]
+mod wasm;
+
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+#[cfg(feature = "serde_json")]
+pub use access::{
+    TypeError, as_array_required, as_bool_required, as_f64_required, as_i64_required, as_object_required,
+    as_str_required, as_u64_required,
+};
+#[cfg(feature = "borrowed")]
+pub use borrowed::BorrowedValue;
+pub use builder::Parser;
+#[cfg(feature = "serde_json")]
+pub use clone::deep_clone;
+pub use config::{DEFAULT_MAX_DEPTH, DuplicateKeys, InvalidEscapePolicy, ParserConfig};
+#[cfg(feature = "serde_json")]
+pub use de::from_str;
+#[cfg(feature = "serde_json")]
+pub use depth::max_depth;
+#[cfg(feature = "serde_json")]
+pub use diff::{Change, diff};
+#[cfg(feature = "serde_json")]
+pub use encoding::parse_auto;
+#[cfg(feature = "serde_json")]
+pub use equal::values_equal;
+pub use error::{ErrorKind, ParseError};
+#[cfg(feature = "serde_json")]
+pub use flatten::{DEFAULT_SEPARATOR, flatten, flatten_with_separator, unflatten, unflatten_with_separator};
+#[cfg(feature = "serde_json")]
+pub use histogram::{TypeCounts, type_histogram};
+pub use lexer::{Lexer, Span, Token, TokenKind};
+#[cfg(feature = "serde_json")]
+pub use leaves::leaves;
+#[cfg(feature = "serde_json")]
+pub use merge::{ArrayStrategy, deep_merge, merge_patch};
+pub use minify::minify;
+#[cfg(feature = "serde_json")]
+pub use number::{as_exact_i64, is_f64, is_i64, is_u64};
+#[cfg(feature = "serde_json")]
+pub use patch::{PatchError, apply_patch};
+#[cfg(feature = "serde_json")]
+pub use path::{PathSeg, get, get_mut};
+#[cfg(feature = "serde_json")]
+pub use pointer::{has_pointer, keys_at, pointer};
+#[cfg(feature = "serde_json")]
+pub use prune::prune_nulls;
+#[cfg(feature = "serde_json")]
+pub use reader::from_reader;
+#[cfg(feature = "tokio")]
+pub use reader::from_async_reader;
+#[cfg(feature = "serde_json")]
+pub use schema::{Field, Schema, SchemaError, SchemaErrorKind, Type, validate_schema};
+#[cfg(feature = "serde_json")]
+pub use ser::{PrettyConfig, to_string, to_string_canonical, to_string_pretty, to_string_pretty_with};
+#[cfg(feature = "serde_json")]
+pub use spanned::{Spanned, SpannedValue};
+pub use stats::ParseStats;
+#[cfg(feature = "serde_json")]
+pub use stats::parse_with_stats;
+#[cfg(feature = "serde_json")]
+pub use stream::{StreamParser, parse_lines, parse_many, stream_array};
+pub use validate::skip_value;
+pub use visit::{Scalar, Visitor, parse_with_visitor};
+#[cfg(feature = "serde_json")]
+pub use wasm::{ErrorInfo, parse_to_json_error};
+
+/// 解析 JSON 文本
+///
+/// 这个函数是库的入口，可以解析任何类型的 JSON 值。
+/// 按 RFC 8259，顶层可以是标量，"42" 和 "\"hi\"" 都是合法的文档；
+/// 只接受对象和数组时使用 Parser::require_root_container。
+/// 整个输入必须恰好是一个 JSON 值，值后面出现非空白内容会返回 TrailingData 错误，
+/// 错误的 offset 指向多余内容的开头，并附带多余内容的一小段预览。
+/// 输入为空或者只有空白时返回 EmptyInput 错误，而不是 UnexpectedEof，
+/// 方便调用方把空输入单独处理，位置指向输入的末尾。
+///
+/// 对象成员按它们在文档中出现的顺序保存（serde_json 的 preserve_order 特性），
+/// 序列化时也按这个顺序输出；需要按键排序时可以用 Parser::sort_keys 或 to_string_canonical。
+///
+/// 举例：
+/// - 输入 "[1, 2, 3]" -> 成功，返回 JSON 数组
+/// - 输入 "{\"b\": 1, \"a\": 2}" -> 成功，键的顺序是 b、a
+/// - 输入 "{\"a\": " -> 失败，返回 ParseError
+/// - 输入 "123 456" -> 失败，"456" 是尾随数据，offset 为 4
+/// - 输入 ""、"   "、"\n\n" -> 失败，返回 EmptyInput
+#[cfg(feature = "serde_json")]
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    parse_with_config(input, &ParserConfig::default())
+}
+
+/// 按指定配置解析 JSON 文本
+///
+/// 与 parse 相同，但可以调整最大嵌套层数等限制。
+/// 通常用 Parser 构建器设置配置更方便。
+///
+/// 输入开头的 UTF-8 字节顺序标记（U+FEFF）会被跳过，
+/// Windows 上的编辑器保存的文件经常带有它。
+/// 出错时的列号不把字节顺序标记算在内，偏移量仍然按原始输入计算。
+///
+/// 举例：
+/// - max_depth 为 2 时，输入 "[[1]]" -> 成功
+/// - max_depth 为 2 时，输入 "[[[1]]]" -> 失败，返回 DepthExceeded
+/// - 输入 "\u{FEFF}{}" -> 成功，返回空对象
+#[cfg(feature = "serde_json")]
+pub fn parse_with_config(input: &str, config: &ParserConfig) -> Result<Value, ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 跳过开头的字节顺序标记
+    parser::parse_document(text, config)
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 按 RFC 8259 严格解析 JSON 文本
+///
+/// 按 ParserConfig::strict 解析：语法与 parse 相同，不允许注释、尾随逗号和单引号字符串，
+/// 值后面出现非空白内容时返回 TrailingData 错误。
+/// 与 parse 不同的是，对象中出现重复的键时返回 DuplicateKey 错误，
+/// 附带键名，位置指向第二次出现的键。
+///
+/// 拒绝重复的键是出于安全考虑的有意选择：不同系统对重复键的处理不一致，
+/// 可能被利用来绕过校验，详见 ParserConfig::strict。
+/// 处理来源不可信的输入时应该使用这个函数；需要按后出现的值覆盖时使用 parse 或 Parser。
+///
+/// 举例：
+/// - 输入 "{\"a\": 1}" -> 成功
+/// - 输入 "{\"a\":1,\"a\":2}" -> 失败，返回 DuplicateKey("a")，位置在第 1 行第 8 列
+/// - 输入 "[1, 2,]" -> 失败，尾随逗号
+/// - 输入 "1 2" -> 失败，"2" 是尾随数据
+#[cfg(feature = "serde_json")]
+pub fn parse_strict(input: &str) -> Result<Value, ParseError> {
+    parse_with_config(input, &ParserConfig::strict())
+}
+
+/// 宽松地解析 JSON 文本
+///
+/// 按 ParserConfig::lenient 解析：允许注释、尾随逗号、单引号字符串和不带引号的键，
+/// 适合手写的配置文件。返回的值和错误类型与 parse_strict 相同，
+/// 值后面出现非空白内容时同样返回 TrailingData 错误。
+///
+/// 举例：
+/// - 输入 "{'a': 1, /* 注释 */ b: [1, 2,],}" -> 成功，返回 {"a": 1, "b": [1, 2]}
+/// - 输入 "[1] // 结尾的注释" -> 成功
+/// - 输入 "[NaN]" -> 失败，NaN 仍然不接受
+#[cfg(feature = "serde_json")]
+pub fn parse_lenient(input: &str) -> Result<Value, ParseError> {
+    parse_with_config(input, &ParserConfig::lenient())
+}
+
+/// 解析 JSON 文本，字符串尽量借用输入
+///
+/// 语法规则与 parse 完全相同，但返回 BorrowedValue：
+/// 不含转义的字符串和键直接借用输入，数字保存原文，
+/// 只有含有转义的字符串才需要分配内存。结果不能比输入活得更久。
+///
+/// 需要打开 borrowed 特性。
+///
+/// 举例：
+/// - 输入 "[\"abc\"]" -> 字符串 "abc" 借用输入
+/// - 输入 "[\"a\\nb\"]" -> 字符串需要解码转义，是新分配的 String
+#[cfg(feature = "borrowed")]
+pub fn parse_borrowed(input: &str) -> Result<BorrowedValue<'_>, ParseError> {
+    borrowed::parse_with_config(input, &ParserConfig::default())
+}
+
+/// 解析 JSON 文本，同时记录每个值在输入中的位置
+///
+/// 语法规则与 parse 完全相同，但返回 Spanned<SpannedValue> 树：
+/// 每个值都带有产生它的文本的字节范围，对象成员的键也带有自己的范围。
+/// 范围按原始输入计算，开头有字节顺序标记时也包括在偏移量里。
+/// 需要普通的 Value 时，调用 into_value 丢掉位置信息即可。
+///
+/// 举例：
+/// - 输入 "[1, \"ab\"]" -> 数组的范围是 0..9，元素的范围分别是 1..2 和 4..8
+/// - 输入 "{\"k\": null}" -> 键的范围是 1..4，值的范围是 6..10
+#[cfg(feature = "serde_json")]
+pub fn parse_spanned(input: &str) -> Result<Spanned<SpannedValue>, ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 跳过开头的字节顺序标记
+    spanned::parse_document(text, input, &ParserConfig::default())
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 解析带注释的 JSON 文本，把注释附加到紧随其后的节点上
+///
+/// 与 parse_spanned 相同，但允许 "//" 行注释和 "/* */" 块注释，
+/// 每个节点前面的注释按原文保存在 Spanned::comments 里，用于编辑带注释的配置文件后原样写回：
+/// - 数组元素前面的注释附加到元素上，对象成员前面的注释附加到成员的键上
+/// - 整个文档前面的注释附加到根节点上
+/// - 值之后、逗号或结束括号之前的注释，以及最后一个元素之后的注释不属于任何节点，会被丢弃
+///
+/// 注释只保存在 SpannedValue 树里，into_value 得到的 serde_json::Value 没有存放注释的地方，
+/// 转换时注释都会丢失。需要打开 comments 特性。
+///
+/// 举例：
+/// - 输入 "{\n  // 端口\n  \"port\": 80\n}" -> 键 "port" 的 comments 为 ["// 端口"]
+/// - 输入 "[/* a */ 1, /* b */ /* c */ 2]" -> 两个元素的 comments 分别为 ["/* a */"] 和 ["/* b */", "/* c */"]
+#[cfg(feature = "comments")]
+pub fn parse_spanned_with_comments(input: &str) -> Result<Spanned<SpannedValue>, ParseError> {
+    let config = ParserConfig { allow_comments: true, ..ParserConfig::default() };
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 跳过开头的字节顺序标记
+    spanned::parse_document(text, input, &config)
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 校验 JSON 文本是否合法，不构造 Value
+///
+/// 语法规则与 parse 完全相同，合法时返回 Ok(())，否则返回与 parse 相同的错误。
+/// 校验过程中不为字符串、数组和对象分配内存，
+/// 适合只需要判断输入是否合法、随后原样转发的场景。
+///
+/// 举例：
+/// - 输入 "{\"a\": [1, 2]}" -> Ok(())
+/// - 输入 "[1, 2" -> 失败，返回 ParseError
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 parse 一样跳过字节顺序标记
+    validate::validate_document(text, &ParserConfig::default())
+        .map(|(_, ())| ())
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 解析字节形式的 JSON 文本
+///
+/// 先校验输入是否为合法的 UTF-8，再按 parse 解析。
+/// 校验只是一次扫描，不会复制输入。
+///
+/// 举例：
+/// - 输入 b"[1, 2]" -> 成功
+/// - 输入 b"\"\xff\"" -> 失败，返回 InvalidUtf8，位置指向第一个非法字节
+#[cfg(feature = "serde_json")]
+pub fn parse_bytes(input: &[u8]) -> Result<Value, ParseError> {
+    let text = core::str::from_utf8(input).map_err(|e| ParseError::invalid_utf8(input, e))?;
+    parse(text)
+}
+
+/// 用非递归解析器解析 JSON 文本
+///
+/// 结果与 parse 完全相同，但数组和对象的嵌套不经过递归，
+/// 而是保存在堆上的显式栈里，嵌套层数只受内存限制，不受 max_depth 约束。
+/// 适合确实需要解析成千上万层嵌套的场景。
+///
+/// 注意：serde_json::Value 的析构本身是递归的，
+/// 释放极深的结果时仍然可能需要足够大的栈。
+///
+/// 举例：
+/// - 输入 10000 层嵌套的数组 -> 成功
+#[cfg(feature = "serde_json")]
+pub fn parse_iterative(input: &str) -> Result<Value, ParseError> {
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 parse 一样跳过字节顺序标记
+    iterative::parse_document(text)
+        .map(|(_, value)| value)  // 只保留解析出的值
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
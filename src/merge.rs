@@ -0,0 +1,87 @@
+//! 合并 JSON 文档
+//!
+//! 按 RFC 7386（JSON Merge Patch）把补丁文档合并进目标文档，
+//! 适合用一份覆盖配置修改基础配置。
+//! 需要拼接数组、或者让 null 覆盖而不是删除时，使用 deep_merge。
+
+use serde_json::{Map, Value};
+
+/// 按 RFC 7386 把 patch 合并进 target
+///
+/// 详细解释：
+/// 1. patch 是对象时，逐个处理它的成员：
+///    - 成员的值是 null：删除 target 中对应的键
+///    - 否则：对 target 中对应的值递归合并，键不存在时视为 null
+///    - target 本身不是对象时，先被替换为空对象
+/// 2. patch 不是对象时（包括数组），整体替换 target
+///
+/// 举例：
+/// - target {"a": "b"}，patch {"a": "c"} -> {"a": "c"}
+/// - target {"a": "b"}，patch {"a": null} -> {}
+/// - target {"a": [1]}，patch {"a": [2]} -> {"a": [2]}，数组整体替换
+/// - target ["a"]，patch {"a": "c"} -> {"a": "c"}
+/// - target {"e": null}，patch {"a": 1} -> {"e": null, "a": 1}，target 中的 null 保留
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(members) = patch else {
+        *target = patch.clone();  // 非对象的补丁整体替换
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());  // 目标不是对象时先替换为空对象
+    }
+    if let Value::Object(map) = target {
+        for (key, value) in members {
+            if value.is_null() {
+                map.shift_remove(key);  // null 表示删除，其余成员保持原来的顺序
+            } else {
+                merge_patch(map.entry(key.clone()).or_insert(Value::Null), value);  // 递归合并
+            }
+        }
+    }
+}
+
+/// deep_merge 遇到两个数组时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayStrategy {
+    /// 用 b 中的数组整体替换 a 中的数组（默认，与 merge_patch 相同）
+    #[default]
+    Replace,
+    /// 把 b 中数组的元素追加到 a 中数组的末尾
+    Concat,
+}
+
+/// 把 b 深度合并进 a
+///
+/// 详细解释：
+/// 1. a 和 b 都是对象时，逐个处理 b 的成员：
+///    - a 中没有这个键：直接插入
+///    - a 中已有这个键：对两个值递归合并
+/// 2. a 和 b 都是数组时，按 array_strategy 替换或拼接
+/// 3. 其他情况（类型不同，或者 b 是标量）用 b 整体替换 a
+///
+/// 与 merge_patch 的区别：b 中的 null 是普通的值，会覆盖 a 中的值而不是删除它；
+/// 数组可以选择拼接；b 按值传入，合并时不需要复制。
+///
+/// 举例：a 为 {"a": {"x": 1, "list": [1]}, "b": 2}
+/// - b 为 {"a": {"y": 2, "list": [2]}}，Replace -> {"a": {"x": 1, "list": [2], "y": 2}, "b": 2}
+/// - b 为 {"a": {"y": 2, "list": [2]}}，Concat -> {"a": {"x": 1, "list": [1, 2], "y": 2}, "b": 2}
+/// - b 为 {"b": null} -> {"a": {"x": 1, "list": [1]}, "b": null}
+/// - b 为 [1] -> [1]
+pub fn deep_merge(a: &mut Value, b: Value, array_strategy: ArrayStrategy) {
+    match (a, b) {
+        (Value::Object(target), Value::Object(members)) => {
+            for (key, value) in members {
+                match target.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value, array_strategy),  // 递归合并
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(target), Value::Array(items)) if array_strategy == ArrayStrategy::Concat => {
+            target.extend(items);  // 拼接
+        }
+        (a, b) => *a = b,  // 其余情况整体替换
+    }
+}
@@ -0,0 +1,41 @@
+//! 压缩 JSON 文本
+//!
+//! 去掉 JSON 文本中所有无意义的空白，不构造任何 Value。
+
+use alloc::string::String;
+
+use crate::{ParseError, validate};
+
+/// 去掉 JSON 文本中所有无意义的空白
+///
+/// 先用 validate 确认输入合法，再逐个字符复制：
+/// 字符串之外的空白被丢弃，字符串内部的内容（包括空白和转义）原样保留。
+/// 因为输入已经通过校验，字符串之外的空白只可能是空格、制表符、回车和换行。
+///
+/// 举例：
+/// - 输入 "{ \"a\" : [ 1 , 2 ] }" -> 输出 "{\"a\":[1,2]}"
+/// - 输入 "[\"a b\" ,\"c\\\" d\"]" -> 输出 "[\"a b\",\"c\\\" d\"]"，字符串中的空白保留
+/// - 输入 "[1, 2" -> 失败，返回与 validate 相同的错误
+pub fn minify(input: &str) -> Result<String, ParseError> {
+    validate(input)?;
+    let input = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 validate 一样跳过字节顺序标记
+    let mut out = String::with_capacity(input.len());
+    let mut in_string = false;  // 当前是否位于字符串内部
+    let mut escaped = false;  // 上一个字符是否是字符串中的反斜杠
+    for c in input.chars() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;  // 转义序列的第二个字符
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;  // 结束引号
+            }
+        } else if !matches!(c, ' ' | '\t' | '\r' | '\n') {
+            out.push(c);
+            in_string = c == '"';  // 开始引号
+        }
+    }
+    Ok(out)
+}
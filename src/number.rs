@@ -0,0 +1,43 @@
+//! 数字分类
+//!
+//! serde_json::Number 自己就有 is_i64、is_u64、is_f64，这里提供直接作用于 Value 的版本，
+//! 不是数字时一律返回 false 或 None，与 json::Value 上的同名方法保持一致。
+
+use serde_json::Value;
+
+use crate::json::exact_i64;
+
+/// 是否为可以用 i64 表示的整数
+///
+/// 举例：
+/// - 1、-1 -> true
+/// - 1.0 -> false，按浮点数保存
+/// - 18446744073709551615 -> false，只能用 u64 表示
+/// - "1" -> false，不是数字
+pub fn is_i64(value: &Value) -> bool {
+    value.is_i64()
+}
+
+/// 是否为可以用 u64 表示的整数，负数和浮点数都是 false
+pub fn is_u64(value: &Value) -> bool {
+    value.is_u64()
+}
+
+/// 是否为按浮点数保存的数字，也就是原文带有小数点或指数，或者超出了整数的范围
+pub fn is_f64(value: &Value) -> bool {
+    value.is_f64()
+}
+
+/// 如果数值恰好是一个 i64 整数则返回它
+///
+/// 与 Value::as_i64 不同，小数部分为 0 的浮点数也算整数；
+/// 有小数部分、超出 i64 范围或者不是数字时返回 None
+///
+/// 举例：
+/// - 3、3.0、3e0 -> Some(3)
+/// - 3.5 -> None
+/// - 1e19 -> None，超出 i64 范围
+pub fn as_exact_i64(value: &Value) -> Option<i64> {
+    let n = value.as_number()?;
+    if n.is_f64() { exact_i64(n.as_f64()?) } else { n.as_i64() }
+}
@@ -0,0 +1,1077 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, tag_no_case, take_while, take_while1, take_while_m_n},
+    character::complete::{char as char_parser, digit1, multispace0, one_of, satisfy},
+    combinator::{all_consuming, cut, map, map_opt, opt, recognize, value, verify},
+    sequence::{delimited, preceded},
+};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::ops::Deref;
+
+use crate::build::{FromJson, KeyCache};
+use crate::config::{DuplicateKeys, InvalidEscapePolicy, ParserConfig};
+use crate::error::{ErrorKind, InputError};
+use crate::stats::ParseStats;
+
+/// 解析器的返回类型，错误统一使用 InputError
+pub(crate) type PResult<'a, T> = IResult<&'a str, T, InputError<'a>>;
+
+/// 解析 null 值
+/// 
+/// 这个函数用于解析 JSON 中的 null 值。
+/// 
+/// 详细解释：
+/// 1. value 函数是 nom 提供的一个解析器组合子，它有两个参数：
+///    - 第一个参数是解析成功时要返回的值（在这里是输出类型的 null 值 V::null()）
+///    - 第二个参数是实际的解析器（在这里是用于匹配 "null" 字符串的解析器）
+/// 
+/// 2. delimited 函数用于处理被其他内容包围的值，它有三个参数：
+///    - 第一个参数 multispace0：匹配零个或多个空白字符（空格、制表符、回车、换行）
+///    - 第二个参数 tag("null")：匹配字符串 "null"
+///    - 第三个参数 multispace0：再次匹配零个或多个空白字符
+/// 
+/// 3. parse(input) 是最终执行解析的方法
+/// 
+/// 举例：
+/// - 输入 "  null  " -> 成功，返回 null 值
+/// - 输入 "null" -> 成功，返回 null 值
+/// - 输入 "nul" -> 失败，不是完整的 "null"
+/// - 输入 "NULL" -> 失败，大小写不匹配
+/// 
+/// 返回值：
+/// - 成功时返回 Ok((剩余输入, V::null()))
+/// - 失败时返回 Err(错误信息)
+pub(crate) fn parse_null<'a, V: FromJson<'a>>(input: &'a str) -> PResult<'a, V> {
+    value( // value 函数的作用是：当解析成功时，返回指定的值
+        V::null(),  // 第一个参数：指定解析成功时要返回的值
+        delimited(
+            multispace0,     // 第一个 delimited 参数：匹配前导空白
+            tag("null"),     // 第二个 delimited 参数：匹配 "null" 字符串
+            multispace0      // 第三个 delimited 参数：匹配尾随空白
+        ),  // delimited 的作用是处理被空白字符包围的 "null" 字符串
+    )  // value 的作用是：当 delimited 解析成功时，返回 null 值
+    .parse(input)  // 对输入字符串执行解析操作
+}
+
+/// 解析布尔值（true 或 false）
+pub(crate) fn parse_bool<'a, V: FromJson<'a>>(input: &'a str) -> PResult<'a, V> {
+    alt((  // 使用 alt 组合器选择两个解析器之一
+        value(
+            V::bool(true),
+            delimited(multispace0, tag("true"), multispace0),  // 匹配 "true"
+        ),
+        value(
+            V::bool(false),
+            delimited(multispace0, tag("false"), multispace0),  // 匹配 "false"
+        ),
+    ))
+    .parse(input)
+}
+
+/// 检查整数部分是否带有多余的前导零
+/// 
+/// JSON 规定整数部分要么是单独的 0，要么以 1-9 开头，
+/// 所以 0 之后紧跟数字就是不合法的
+/// 
+/// 举例：
+/// - "0"、"-0"、"0.5"、"0e1"、"10" -> false
+/// - "01"、"-007"、"01.5" -> true
+fn has_leading_zero(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);  // 去掉负号，只看整数部分
+    digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// 检查小数部分和指数部分是否完整
+/// 
+/// JSON 规定小数点之后至少有一位数字，
+/// 指数由 e 或 E、可选的 + 或 -、以及至少一位数字组成。
+/// recognize_number 识别时允许这些部分为空，这里把它们挑出来
+/// 
+/// 举例：
+/// - "1.5"、"1e10"、"1E-5"、"1.5e+3" -> false
+/// - "1.e5"、"1."、"1e"、"1e+" -> true
+fn has_incomplete_part(s: &str) -> bool {
+    let starts_with_digit = |t: &str| t.starts_with(|c: char| c.is_ascii_digit());
+    let fraction = s.find('.').map(|i| &s[i + 1..]);  // 小数点之后的内容
+    let exponent = s.find(['e', 'E']).map(|i| {
+        let exponent = &s[i + 1..];
+        exponent.strip_prefix(['+', '-']).unwrap_or(exponent)  // 最多一个符号
+    });
+    [fraction, exponent].into_iter().flatten().any(|part| !starts_with_digit(part))  // 小数点或指数之后没有数字
+}
+
+/// 把数字文本拆成符号、有效数字和十进制指数，值等于 有效数字 × 10^指数
+///
+/// 有效数字去掉了开头和末尾的 0，数值相等的文本拆出的结果相同；
+/// 零的有效数字为空，符号和指数都记为 false 和 0。
+/// 指数超出 i64 范围时返回 None
+///
+/// 举例：
+/// - "-1.50e2" -> (true, "15", 1)
+/// - "0.0012" -> (false, "12", -4)
+/// - "-0.0" -> (false, "", 0)
+fn decimal_parts(s: &str) -> Option<(bool, String, i64)> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = alloc::format!("{}{}", int, frac);
+    let digits = digits.trim_start_matches('0');
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        return Some((false, String::new(), 0));  // 零
+    }
+    let shift = i64::try_from(frac.len()).ok()? - i64::try_from(digits.len() - trimmed.len()).ok()?;  // 小数位数减去末尾的 0
+    Some((negative, String::from(trimmed), exponent.checked_sub(shift)?))
+}
+
+/// 检查数字文本经过 f64 之后能否还原
+///
+/// 把文本转换为 f64，再按能够还原这个 f64 的最短形式输出，比较两者的数值是否相同。
+/// 这与它在 JavaScript 等只有 f64 的环境里经过一次解析和输出的结果一致
+///
+/// 举例：
+/// - "0.1"、"1e300"、"9007199254740992" -> true
+/// - "9007199254740993" -> false，最接近的 f64 是 9007199254740992
+/// - "1e-400" -> false，下溢为 0
+/// - "18446744073709551616" -> false，最短形式是 1.8446744073709552e19
+fn round_trips_f64(s: &str) -> bool {
+    let Ok(f) = s.parse::<f64>() else { return false };
+    if !f.is_finite() {
+        return false;
+    }
+    let shortest = alloc::format!("{:e}", f);  // 最短的科学计数法形式，如 9.007199254740992e15
+    match (decimal_parts(s), decimal_parts(&shortest)) {
+        (Some(original), Some(round_trip)) => original == round_trip,
+        _ => false,
+    }
+}
+
+/// 识别 JSON 数字的文本
+/// 
+/// 格式为：可选的负号、至少一位整数数字、可选的小数部分、可选的指数部分。
+/// 开头必须是负号或数字，所以 "+5"、".5"、"-.5" 都不会被识别为数字，返回普通错误。
+/// 小数点和指数之后的数字允许为空，交给 has_incomplete_part 报告 InvalidNumber，
+/// 这样 "5." 这样的输入得到准确的错误类别，而不是笼统的尾随数据。
+/// 
+/// 注意这里用 opt(digit1) 而不是 digit0：nom 8.0 的 digit0 在数字一直持续到输入末尾时，
+/// 返回的剩余输入指向数字的开头，recognize 会因此少算这些数字
+/// 
+/// 举例：
+/// - "-12.5e3]" -> 识别出 "-12.5e3"
+/// - "5." -> 识别出 "5."，随后的检查会拒绝它
+/// - "+5"、".5" -> 失败
+fn recognize_number(input: &str) -> PResult<'_, &str> {
+    recognize((
+        opt(char_parser('-')),  // 只允许负号
+        digit1,  // 整数部分至少一位数字
+        opt((char_parser('.'), opt(digit1))),  // 小数部分
+        opt((one_of("eE"), opt(one_of("+-")), opt(digit1))),  // 指数部分
+    ))
+    .parse(input)
+}
+
+/// 解析 JSON 中的数字值
+/// 
+/// 这个函数用于解析 JSON 中的数字（整数或浮点数）。
+/// 
+/// 详细解释：
+/// 1. 识别过程：
+///    - multispace0：匹配前面的空白字符（比如：" 123" 中的空格）
+///    - recognize_number：匹配数字字符串（比如："123.45"）
+///    - multispace0：匹配后面的空白字符（比如："123 " 中的空格）
+/// 
+/// 2. 识别出的字符串还要经过 JSON 语法检查：
+///    - 整数部分以 0 开头时，0 之后只能是小数点、指数或结束（"007"、"01.5" 不合法）
+///    - 小数点之后至少有一位数字（"1.e5"、"1." 不合法）
+///    - 指数是 e 或 E、可选的符号和至少一位数字（"1e"、"1e+" 不合法）
+/// 
+/// 3. 字符串到数字的转换由输出类型的 FromJson::number 完成
+/// 
+/// 4. 设置了 max_number_len 时，识别出的数字文本超过这个长度直接返回 Failure(ResourceLimit)，
+///    不会再尝试转换
+/// 
+/// 5. 打开 reject_lossy_numbers 时，经过 f64 之后无法还原的数字返回 Failure(LossyNumber)
+/// 
+/// 6. 识别和转换分成两步，是为了区分两种失败：
+///    - 识别失败：输入根本不是数字，返回普通错误，alt 会继续尝试其他解析器
+///    - 检查或转换失败：输入看起来是数字但不合法或无法表示，返回 Failure，直接报告 InvalidNumber
+/// 
+/// 举例：
+/// - 输入：" 123.45 " 
+///   1) 去掉前后空格
+///   2) 识别 "123.45" 为数字
+///   3) 转换为 JSON 数字值
+/// - 输入："42" -> 整数 42，而不是浮点数 42.0
+/// - 输入："0"、"0.5"、"10" -> 成功
+/// - 输入："01"、"007" -> 失败，存在多余的前导零
+/// - 输入："1e10"、"1E-5"、"1.5e+3" -> 成功
+/// - 输入："1e"、"1e+"、"1.e5" -> 失败，指数或小数部分不完整
+/// - 输入："-0" -> 成功
+/// - 输入："+5"、".5"、"-.5" -> 失败，不是数字
+/// - 输入："5." -> 失败，小数点之后没有数字
+/// - 输入："1e999" -> 失败，结果是无穷大，JSON 无法表示
+/// 
+/// 错误处理：
+/// - 如果输入不是有效的数字格式，将返回错误
+/// - 如果数字不符合 JSON 语法（如前导零、不完整的小数或指数），返回 InvalidNumber
+/// - 如果数字无法转换为 JSON 数字类型（如溢出为无穷大），返回 InvalidNumber，不会 panic
+/// - 如果数字文本超过 max_number_len，返回 ResourceLimit，位置指向数字的开头
+/// - 如果打开了 reject_lossy_numbers 而数字经过 f64 之后无法还原，返回 LossyNumber
+pub(crate) fn parse_number<'a, V: FromJson<'a>>(input: &'a str, config: &ParserConfig) -> PResult<'a, V> {
+    let (input, _) = multispace0(input)?;  // 第一步：匹配前导空白（例如："  123" 中的空格）
+    let (rest, s) = recognize_number(input)?;  // 第二步：识别数字字符串（例如："-123.45" 或 "42"）
+    if has_leading_zero(s) || has_incomplete_part(s) {  // 第三步：检查 JSON 数字语法
+        return Err(nom::Err::Failure(InputError::new(input, ErrorKind::InvalidNumber)));
+    }
+    if config.max_number_len.is_some_and(|max| s.len() > max) {  // 转换之前先检查长度
+        return Err(nom::Err::Failure(InputError::new(input, ErrorKind::ResourceLimit)));
+    }
+    if config.reject_lossy_numbers && !round_trips_f64(s) {  // 转换为 f64 会丢失精度
+        return Err(nom::Err::Failure(InputError::new(input, ErrorKind::LossyNumber)));
+    }
+    match V::number(s, config) {  // 第四步：将字符串转为 JSON 数字
+        Some(number) => {
+            let (rest, _) = multispace0(rest)?;  // 匹配尾随空白（例如："123  " 中的空格）
+            Ok((rest, number))
+        }
+        None => Err(nom::Err::Failure(InputError::new(input, ErrorKind::InvalidNumber))),
+    }
+}
+
+/// 按配置解析数字，再交给 ctx 中的校验函数检查
+/// 
+/// 校验函数返回 Err(reason) 时返回 Failure(RejectedNumber(reason))，位置指向数字的开头；
+/// 没有校验函数时与 parse_number 相同
+fn parse_validated_number<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>) -> PResult<'a, V> {
+    let (rest, number) = parse_number::<V>(input, ctx)?;
+    #[cfg(feature = "serde_json")]
+    if let (Some(validator), Some(n)) = (ctx.number_validator, number.serde_number()) {  // 直接检查已经构造好的数字，不再转换一次
+        let start = input.trim_start_matches([' ', '\t', '\r', '\n']);  // 数字解析器会吃掉前导空白
+        validator(n).map_err(|reason| nom::Err::Failure(InputError::new(start, ErrorKind::RejectedNumber(reason))))?;
+    }
+    Ok((rest, number))
+}
+
+/// 不区分大小写地解析 true、false 和 null
+/// 
+/// 只有 case_insensitive_literals 打开时才接受，否则返回普通错误，
+/// 小写的写法已经由 parse_bool 和 parse_null 处理，这里只需要补上其他大小写组合。
+/// 
+/// 举例（case_insensitive_literals 模式）：
+/// - 输入 "TRUE" -> 成功，返回 true
+/// - 输入 "False" -> 成功，返回 false
+/// - 输入 "NULL" -> 成功，返回 null
+/// - 输入 "Nul" -> 失败，不是完整的字面量
+pub(crate) fn parse_literal_no_case<'a, V: FromJson<'a>>(input: &'a str, config: &ParserConfig) -> PResult<'a, V> {
+    if !config.case_insensitive_literals {
+        return Err(nom::Err::Error(InputError::new(input, ErrorKind::UnexpectedChar)));  // 严格模式
+    }
+    delimited(
+        multispace0,
+        alt((
+            value(V::bool(true), tag_no_case("true")),
+            value(V::bool(false), tag_no_case("false")),
+            value(V::null(), tag_no_case("null")),
+        )),
+        multispace0,
+    ).parse(input)
+}
+
+/// 解析非有限数字字面量 NaN、Infinity 和 -Infinity
+/// 
+/// 标准 JSON 不允许这些字面量，但 Python 的 json 模块（allow_nan=True）等会输出它们。
+/// 只有 allow_nan 打开时才接受，否则返回普通错误，由其他解析器继续尝试。
+/// 
+/// serde_json::Number 只能表示有限的数字，无法保存 NaN 和无穷大，
+/// 所以这三个字面量都解析为 null。
+/// 这与 serde_json 把 NaN 和无穷大序列化为 null 的做法一致。
+/// 
+/// 举例（allow_nan 模式）：
+/// - 输入 "NaN" -> 成功，返回 null
+/// - 输入 "-Infinity" -> 成功，返回 null
+/// - 输入 "nan" -> 失败，大小写必须一致
+pub(crate) fn parse_non_finite<'a, V: FromJson<'a>>(input: &'a str, config: &ParserConfig) -> PResult<'a, V> {
+    if !config.allow_nan {
+        return Err(nom::Err::Error(InputError::new(input, ErrorKind::UnexpectedChar)));  // 严格模式
+    }
+    value(
+        V::null(),  // 无法用 JSON 数字表示，统一返回 null
+        alt((tag("NaN"), tag("Infinity"), tag("-Infinity"))),
+    ).parse(input)
+}
+
+/// 解析四位十六进制数字，返回对应的 UTF-16 码元
+fn parse_hex4(input: &str) -> PResult<'_, u32> {
+    map_opt(
+        take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),  // 恰好四位十六进制数字
+        |hex: &str| u32::from_str_radix(hex, 16).ok(),  // 按十六进制解析
+    ).parse(input)
+}
+
+/// 解析 Unicode 转义
+/// 处理 \uXXXX 形式的转义，'u' 之后必须恰好是四位十六进制数字
+/// 
+/// 详细解释：
+/// 1. 基本多文种平面的字符直接由一个 \uXXXX 表示
+/// 2. 表情符号等辅助平面字符由 UTF-16 代理对表示，例如 "\uD83D\uDE00"：
+///    - 高代理项范围 0xD800–0xDBFF，其后必须紧跟 \u 加低代理项
+///    - 低代理项范围 0xDC00–0xDFFF
+///    - 合并公式：0x10000 + ((高 - 0xD800) << 10) + (低 - 0xDC00)
+/// 3. 单独出现的高代理项或低代理项都视为解析错误
+/// 
+/// 举例：
+/// - 输入 "u00e9" -> 成功，返回 'é'
+/// - 输入 "uD83D\uDE00" -> 成功，返回 '😀'
+/// - 输入 "u00e" -> 失败，十六进制数字不足四位
+/// - 输入 "uD83D" -> 失败，高代理项后缺少低代理项
+/// - 输入 "uDE00" -> 失败，单独的低代理项
+fn parse_unicode_escape(input: &str) -> PResult<'_, char> {
+    let (input, _) = char_parser('u')(input)?;  // 匹配 'u'
+    let (rest, high) = parse_hex4(input)?;  // 第一个码元
+    let (rest, code) = match high {
+        0xD800..=0xDBFF => {  // 高代理项：必须紧跟低代理项
+            let (rest, low) = map_opt(
+                preceded(tag("\\u"), parse_hex4),
+                |low| (0xDC00..=0xDFFF).contains(&low).then_some(low),
+            ).parse(rest)?;
+            (rest, 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))  // 合并代理对
+        }
+        _ => (rest, high),  // 普通码点
+    };
+    match char::from_u32(code) {  // 单独的低代理项在这里返回 None
+        Some(c) => Ok((rest, c)),
+        None => Err(nom::Err::Error(InputError::new(input, ErrorKind::InvalidEscape))),
+    }
+}
+
+/// 解析转义字符
+/// 处理 JSON 字符串中的特殊字符，如 \n, \t, \uXXXX 等
+/// 
+/// quote 是包围字符串的引号，单引号字符串中额外允许 \' 转义
+/// 
+/// 反斜杠之后的内容一旦无法识别，就用 cut 直接报告错误，而不是回退到普通字符分支：
+/// - 输入在转义序列中间结束（如 "abc\ 或 "\u12）：UnexpectedEof，位置指向输入末尾，
+///   parse_quoted_string 再把它报告为 UnterminatedString
+/// - 格式错误的 \uXXXX：InvalidEscape，位置指向反斜杠
+/// - 反斜杠后面是不认识的字符（如 \x）：按 policy 处理，
+///   Error 报告 InvalidEscape，KeepLiteral 返回该字符，Replace 返回 U+FFFD；
+///   控制字符（如反斜杠后面直接换行）在任何 policy 下都报告 InvalidEscape
+pub(crate) fn parse_escaped_char(input: &str, quote: char, policy: InvalidEscapePolicy) -> PResult<'_, char> {
+    let (rest, _) = char_parser('\\')(input)?;  // 首先匹配反斜杠
+    if let Some(c) = rest.chars().next().filter(|&c| policy != InvalidEscapePolicy::Error && !is_known_escape(c, quote) && is_plain_char(c, quote)) {
+        let replacement = if policy == InvalidEscapePolicy::Replace { '\u{FFFD}' } else { c };
+        return Ok((&rest[c.len_utf8()..], replacement));  // 不认识的转义，按 policy 宽松处理
+    }
+    cut(alt((  // 然后匹配以下转义字符之一
+        value('\"', char_parser('\"')),  // 引号
+        value('\\', char_parser('\\')),  // 反斜杠
+        value('/', char_parser('/')),    // 斜杠
+        value('\n', char_parser('n')),   // 换行
+        value('\r', char_parser('r')),   // 回车
+        value('\t', char_parser('t')),   // 制表符
+        value('\u{0008}', char_parser('b')),  // 退格
+        value('\u{000C}', char_parser('f')),  // 换页
+        parse_unicode_escape,                 // Unicode 转义 \uXXXX
+        value('\'', verify(char_parser('\''), |_| quote == '\'')),  // 单引号，只用于单引号字符串
+    )))
+    .parse(rest)
+    .map_err(|e| {
+        e.map(|_| {
+            if is_truncated_escape(rest) {
+                InputError::new(&rest[rest.len()..], ErrorKind::UnexpectedEof)  // 转义序列被截断
+            } else {
+                InputError::new(input, ErrorKind::InvalidEscape)  // 无效转义
+            }
+        })
+    })
+}
+
+/// 判断 c 能否紧跟在反斜杠后面构成合法的转义序列
+fn is_known_escape(c: char, quote: char) -> bool {
+    matches!(c, '"' | '\\' | '/' | 'n' | 'r' | 't' | 'b' | 'f' | 'u') || (c == '\'' && quote == '\'')
+}
+
+/// 判断反斜杠之后的内容是否是一个被输入末尾截断的转义序列
+/// 
+/// 举例：
+/// - ""、"u"、"u12"：截断，后面再补上内容就可能合法
+/// - "uD83D"、"uD83D\"、"uD83D\uDE"：截断，高代理项后面的低代理项不完整
+/// - "q"、"u12x"：不是截断，而是无效的转义
+fn is_truncated_escape(rest: &str) -> bool {
+    let Some(hex) = rest.strip_prefix('u') else {
+        return rest.is_empty();  // 反斜杠位于输入末尾
+    };
+    let digits = hex.bytes().take_while(u8::is_ascii_hexdigit).count();
+    if digits < 4 {
+        return digits == hex.len();  // 十六进制数字一直持续到输入末尾
+    }
+    let high = u32::from_str_radix(&hex[..4], 16).unwrap_or(0);
+    if !(0xD800..=0xDBFF).contains(&high) {
+        return false;  // 完整的普通码点，不是截断造成的错误
+    }
+    let tail = &hex[4..];  // 高代理项之后应该是 \u 加四位十六进制数字
+    match tail.strip_prefix("\\u") {
+        Some(low) => low.len() < 4 && low.bytes().all(|b| b.is_ascii_hexdigit()),
+        None => "\\u".starts_with(tail),  // "" 或 "\\"
+    }
+}
+
+/// 判断字符能否不经转义直接出现在字符串中
+/// 引号和反斜杠有特殊含义，U+0000 到 U+001F 的控制字符按 RFC 8259 必须转义
+/// 
+/// quote 是包围字符串的引号，只有它需要转义，
+/// 例如单引号字符串中可以直接出现双引号
+pub(crate) fn is_plain_char(c: char, quote: char) -> bool {
+    c != quote && c != '\\' && c >= '\u{0020}'
+}
+
+/// 字符串内容的一个片段
+enum StringFragment<'a> {
+    /// 一段不含转义的普通字符，直接从输入中借用
+    Literal(&'a str),
+    /// 一个转义序列解码后的字符
+    Escaped(char),
+}
+
+/// 解析由 quote 包围的字符串
+/// 处理普通字符和转义字符
+/// 
+/// 连续的普通字符用 take_while1 一次识别出来，只有遇到反斜杠时才逐个解码转义序列。
+/// 不含转义的字符串只有一个片段，结果直接借用输入中的原文，不分配内存；
+/// 含有转义时才需要拼接出新的字符串。
+/// 
+/// max_len 限制解码后字符串的字节数，每追加一个片段就检查一次，
+/// 超过时立即返回 Failure(ResourceLimit)，位置指向开始引号，不会先把整个字符串读完。
+/// 
+/// 字符串中直接出现的控制字符（如未转义的换行、制表符）会导致解析失败，
+/// 必须写成 \n、\t 等转义形式，出错位置指向该控制字符
+/// 
+/// 到输入末尾（包括在转义序列中间）都没有遇到结束引号时，返回 Failure(UnterminatedString)，
+/// 位置指向开始引号，而不是输入末尾，这样能直接看出是哪个字符串少了引号
+/// 
+/// 举例：
+/// - 输入 "{\"name\": \"John}" -> 失败，UnterminatedString，位置是 "John 前面的引号
+fn parse_quoted_string(input: &str, quote: char, max_len: Option<usize>, policy: InvalidEscapePolicy) -> PResult<'_, Cow<'_, str>> {
+    let (mut rest, _) = char_parser(quote)(input)?;  // 开始引号
+    let mut s = Cow::Borrowed("");
+    loop {
+        let fragment = alt((
+            map(take_while1(|c| is_plain_char(c, quote)), StringFragment::Literal),  // 一段普通字符（非引号、反斜杠和控制字符）
+            map(|i| parse_escaped_char(i, quote, policy), StringFragment::Escaped),  // 转义字符
+        )).parse(rest);
+        let (next, fragment) = match fragment {
+            Ok(ok) => ok,
+            Err(nom::Err::Error(_)) => break,  // 没有更多片段，接下来应该是结束引号
+            Err(e) => return Err(unterminated_string(input, e)),  // 无效或被截断的转义
+        };
+        match fragment {
+            StringFragment::Literal(text) if s.is_empty() => s = Cow::Borrowed(text),  // 第一个片段直接借用
+            StringFragment::Literal(text) => s.to_mut().push_str(text),  // 整段复制
+            StringFragment::Escaped(c) => s.to_mut().push(c),
+        }
+        if max_len.is_some_and(|max| s.len() > max) {
+            return Err(nom::Err::Failure(InputError::new(input, ErrorKind::ResourceLimit)));  // 字符串太长
+        }
+        rest = next;
+    }
+    let (rest, _) = closing_quote(input, rest, quote)?;
+    Ok((rest, s))
+}
+
+/// 把字符串内部的 UnexpectedEof（转义序列被输入末尾截断）换成 UnterminatedString
+/// open 是开始引号的位置，其他错误原样返回
+pub(crate) fn unterminated_string<'a>(open: &'a str, e: nom::Err<InputError<'a>>) -> nom::Err<InputError<'a>> {
+    match e {
+        nom::Err::Failure(err) if err.kind == ErrorKind::UnexpectedEof => {
+            nom::Err::Failure(InputError::new(open, ErrorKind::UnterminatedString))
+        }
+        e => e,
+    }
+}
+
+/// 匹配字符串的结束引号
+/// 
+/// open 是开始引号的位置，rest 是字符串内容之后的输入。
+/// rest 为空时返回 Failure(UnterminatedString)，位置指向开始引号；
+/// 其他情况用 cut 匹配结束引号，匹配到开始引号之后就不再回退
+pub(crate) fn closing_quote<'a>(open: &'a str, rest: &'a str, quote: char) -> PResult<'a, char> {
+    if rest.is_empty() {
+        return Err(nom::Err::Failure(InputError::new(open, ErrorKind::UnterminatedString)));  // 到输入末尾都没有结束引号
+    }
+    cut(char_parser(quote)).parse(rest)
+}
+
+/// 解析字符串
+/// 标准 JSON 字符串只能用双引号包围，长度不受限制
+pub(crate) fn parse_string(input: &str) -> PResult<'_, Cow<'_, str>> {
+    parse_quoted_string(input, '"', None, InvalidEscapePolicy::Error)
+}
+
+/// 按配置解析字符串
+/// 
+/// json5 打开时，字符串也可以用单引号包围，结束引号必须与开始引号一致
+/// 
+/// 举例（json5 模式）：
+/// - 输入 "'hello'" -> 成功，返回 "hello"
+/// - 输入 "'it\\'s'" -> 成功，返回 "it's"
+/// - 输入 "'say \"hi\"'" -> 成功，单引号字符串中的双引号不需要转义
+/// - 输入 "'hello\"" -> 失败，引号不匹配
+/// 
+/// 字符串解码后的字节数超过 max_string_len 时返回 ResourceLimit，
+/// 无法识别的转义按 invalid_escape 处理
+pub(crate) fn parse_string_with<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, Cow<'a, str>> {
+    let (max_len, policy) = (config.max_string_len, config.invalid_escape);
+    if config.json5 {
+        alt((|i| parse_quoted_string(i, '"', max_len, policy), |i| parse_quoted_string(i, '\'', max_len, policy))).parse(input)
+    } else {
+        parse_quoted_string(input, '"', max_len, policy)
+    }
+}
+
+/// 解析不带引号的标识符键
+/// 标识符以字母、'_' 或 '$' 开头，后面可以跟字母、数字、'_' 或 '$'，
+/// 即 [A-Za-z_$][A-Za-z0-9_$]*
+fn parse_identifier(input: &str) -> PResult<'_, Cow<'_, str>> {
+    map(recognize_identifier, Cow::Borrowed).parse(input)  // 标识符中没有转义，直接借用原文
+}
+
+/// 识别不带引号的标识符，返回标识符在输入中的原文
+pub(crate) fn recognize_identifier(input: &str) -> PResult<'_, &str> {
+    recognize((
+        satisfy(|c| c.is_ascii_alphabetic() || c == '_' || c == '$'),  // 首字符
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$'),  // 其余字符
+    )).parse(input)
+}
+
+/// 按配置解析对象的键
+/// 
+/// 键必须是字符串；json5 打开时，键也可以是不带引号的标识符
+/// 
+/// 举例（json5 模式）：
+/// - 输入 "name" -> 成功，返回 "name"
+/// - 输入 "$id_2" -> 成功，返回 "$id_2"
+/// - 输入 "2nd" -> 失败，标识符不能以数字开头
+pub(crate) fn parse_key<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, Cow<'a, str>> {
+    if config.json5 {
+        alt((|i| parse_string_with(i, config), parse_identifier)).parse(input)
+    } else {
+        parse_string_with(input, config)  // 键和字符串值一样受 max_string_len 限制
+    }
+}
+
+/// 跳过空白，allow_comments 为 true 时同时跳过注释
+/// 
+/// 支持两种注释：
+/// - 行注释：从 "//" 到行尾
+/// - 块注释：从 "/*" 到 "*/"，块注释没有结束时返回 UnexpectedEof
+/// 
+/// 空白和注释可以任意交替出现，例如 " // a\n /* b */ "
+///
+/// 这里的空白只包括 RFC 8259 规定的空格、制表符、回车和换行，
+/// multispace0 恰好只匹配这四个字符。U+00A0 不换行空格、U+FEFF 等
+/// 其他 Unicode 空白不会被跳过，会由后面的解析器报告为意外的字符。
+pub(crate) fn skip_whitespace(input: &str, allow_comments: bool) -> PResult<'_, ()> {
+    let (mut input, _) = multispace0(input)?;  // 普通空白
+    if !allow_comments {
+        return Ok((input, ()));  // 严格模式下 "//" 不是空白，留给后面的解析器报错
+    }
+    loop {
+        if let Some(comment) = input.strip_prefix("//") {
+            input = comment.find('\n').map_or("", |i| &comment[i..]);  // 行注释：跳到换行符
+        } else if let Some(comment) = input.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(i) => input = &comment[i + 2..],  // 块注释：跳过结束标记
+                None => {
+                    let end = &comment[comment.len()..];  // 块注释一直持续到输入结束
+                    return Err(nom::Err::Failure(InputError::new(end, ErrorKind::UnexpectedEof)));
+                }
+            }
+        } else {
+            return Ok((input, ()));  // 既不是空白也不是注释
+        }
+        let (rest, _) = multispace0(input)?;  // 注释之后的空白
+        input = rest;
+    }
+}
+
+/// 按配置跳过空白（以及注释）的解析器
+/// 用来替代容器和顶层解析中的 multispace0
+pub(crate) fn ws<'a>(config: &ParserConfig) -> impl Fn(&'a str) -> PResult<'a, ()> {
+    let allow_comments = config.allow_comments;
+    move |input| skip_whitespace(input, allow_comments)
+}
+
+/// 检查解析出的数字的校验函数，返回 Err 时附带拒绝的原因
+#[cfg(feature = "serde_json")]
+pub(crate) type NumberCheck<'c> = &'c dyn Fn(&serde_json::Number) -> Result<(), String>;
+
+/// 一次解析过程中共享的状态
+/// 
+/// 除了配置之外，还记录已经解析出的值的个数，用于检查 max_elements，
+/// 打开 intern_keys 时还保存对象键的缓存，由 with_stats 创建时还顺带收集 ParseStats。
+/// 通过 Deref 可以直接当作 ParserConfig 使用，只需要配置的解析器不必关心它。
+pub(crate) struct Context<'c> {
+    /// 解析配置
+    config: &'c ParserConfig,
+    /// 已经解析出的值的个数
+    elements: Cell<usize>,
+    /// 打开 intern_keys 时共享的对象键
+    keys: Option<KeyCache>,
+    /// 由 with_stats 创建时收集的统计信息
+    stats: Option<Cell<ParseStats>>,
+    /// 对每个解析出的数字调用的校验函数，返回 Err 时解析失败
+    #[cfg(feature = "serde_json")]
+    number_validator: Option<NumberCheck<'c>>,
+}
+
+impl<'c> Context<'c> {
+    /// 为一次新的解析创建状态
+    pub(crate) fn new(config: &'c ParserConfig) -> Self {
+        let keys = config.intern_keys.then(KeyCache::default);
+        Context {
+            config,
+            elements: Cell::new(0),
+            keys,
+            stats: None,
+            #[cfg(feature = "serde_json")]
+            number_validator: None,
+        }
+    }
+
+    /// 为一次新的解析创建状态，并在解析过程中收集统计信息
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn with_stats(config: &'c ParserConfig) -> Self {
+        Context { stats: Some(Cell::new(ParseStats::default())), ..Context::new(config) }
+    }
+
+    /// 为一次新的解析创建状态，每解析出一个数字就用 validator 检查它
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn with_number_validator(config: &'c ParserConfig, validator: NumberCheck<'c>) -> Self {
+        Context { number_validator: Some(validator), ..Context::new(config) }
+    }
+
+    /// 到目前为止收集到的统计信息，不是由 with_stats 创建时返回 None
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn stats(&self) -> Option<ParseStats> {
+        self.stats.as_ref().map(Cell::get)
+    }
+
+    /// 把一个解析出的值计入统计信息
+    ///
+    /// input 是这个值开始的位置，按第一个字符判断类型；depth 与 parse_primary 相同，
+    /// 数组和对象自身位于第 depth + 1 层
+    fn record(&self, input: &str, depth: usize) {
+        let Some(cell) = &self.stats else { return };
+        let mut stats = cell.get();
+        match input.as_bytes().first() {
+            Some(b'{') => stats.objects += 1,
+            Some(b'[') => stats.arrays += 1,
+            Some(b'"' | b'\'') => stats.strings += 1,
+            Some(b't' | b'f' | b'n') => {}  // 布尔值和 null 不计入
+            _ => stats.numbers += 1,  // 数字，包括 allow_nan 时的 NaN 和无穷大
+        }
+        if input.starts_with(['{', '[']) {
+            stats.max_depth = stats.max_depth.max(depth + 1);
+        }
+        cell.set(stats);
+    }
+
+    /// 记录解析出了一个值，超过 max_elements 时返回 Failure(ResourceLimit)
+    /// input 是这个值开始的位置，用于报告出错的地方
+    fn count_element<'a>(&self, input: &'a str) -> Result<(), nom::Err<InputError<'a>>> {
+        let count = self.elements.get() + 1;
+        self.elements.set(count);
+        match self.config.max_elements {
+            Some(max) if count > max => Err(nom::Err::Failure(InputError::new(input, ErrorKind::ResourceLimit))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 统计当前容器中已经解析出的元素或成员，超过 max 时返回 Failure(ResourceLimit)
+///
+/// count 是这个容器自己的计数，每解析出一个元素调用一次；
+/// input 是这个元素开始的位置，用于报告出错的地方
+fn count_entry<'a>(max: Option<usize>, count: &Cell<usize>, input: &'a str) -> Result<(), nom::Err<InputError<'a>>> {
+    count.set(count.get() + 1);
+    match max {
+        Some(max) if count.get() > max => Err(nom::Err::Failure(InputError::new(input, ErrorKind::ResourceLimit))),
+        _ => Ok(()),
+    }
+}
+
+impl Deref for Context<'_> {
+    type Target = ParserConfig;
+
+    fn deref(&self) -> &ParserConfig {
+        self.config
+    }
+}
+
+/// 检查嵌套层数
+/// 
+/// depth 是当前已经进入的数组/对象层数，
+/// 再进入一层会超过 max_depth 时返回 Failure(DepthExceeded)，
+/// 在递归发生之前就停下来，避免栈溢出
+pub(crate) fn check_depth<'a>(config: &ParserConfig, depth: usize) -> impl Fn(&'a str) -> PResult<'a, ()> {
+    let max_depth = config.max_depth;
+    move |input| {
+        if depth >= max_depth {
+            Err(nom::Err::Failure(InputError::new(input, ErrorKind::DepthExceeded)))
+        } else {
+            Ok((input, ()))
+        }
+    }
+}
+
+/// 判断容器内部在 rest 处的失败是不是因为输入在容器结束之前就用完了
+/// 
+/// before 是容器中出错位置之前的内容。跳过空白（以及注释）和最多一个逗号之后什么都没有时是，
+/// 逗号只能跟在元素或成员之后，所以 "[," 和 "[1,," 不算；
+/// 对象中还允许最后剩下一个键和可选的冒号，例如 {"a" 或 {"a": 1, "b"
+fn is_cut_off(before: &str, rest: &str, object: bool, config: &ParserConfig) -> bool {
+    fn skip<'a>(s: &'a str, config: &ParserConfig) -> &'a str {
+        ws(config)(s).map_or("", |(r, _)| r)  // 块注释没有结束，同样是输入用完了
+    }
+    let after_entry = !before.trim_end_matches([' ', '\t', '\r', '\n']).ends_with(['[', '{', ',']);  // 前面是一个元素或成员
+    let rest = skip(rest, config);
+    let rest = match rest.strip_prefix(',') {
+        Some(r) if after_entry => skip(r, config),  // 最多一个逗号，而且只能跟在元素或成员之后
+        _ => rest,
+    };
+    if rest.is_empty() {
+        return true;
+    }
+    if !object {
+        return false;
+    }
+    let Ok((after, _)) = parse_key(rest, config) else { return false };  // 只剩下一个键
+    let after = skip(after, config);
+    skip(after.strip_prefix(':').unwrap_or(after), config).is_empty()  // 以及可选的冒号
+}
+
+/// 把容器内部因为输入用完而产生的错误换成 UnterminatedArray 或 UnterminatedObject
+/// 
+/// open 指向开始方括号或大括号。只有 UnexpectedEof 和 UnexpectedChar 会被替换，
+/// 而且出错位置之后必须已经没有实质内容（见 is_cut_off）；
+/// 其他错误，包括内层容器或字符串已经报告的 Unterminated 错误，都原样返回，
+/// 所以报告的总是最里面那个没有结束的容器
+pub(crate) fn unterminated_container<'a>(open: &'a str, e: nom::Err<InputError<'a>>, config: &ParserConfig) -> nom::Err<InputError<'a>> {
+    let object = open.starts_with('{');
+    match e {
+        nom::Err::Error(err) | nom::Err::Failure(err)
+            if matches!(err.kind, ErrorKind::UnexpectedEof | ErrorKind::UnexpectedChar)
+                && is_cut_off(open.get(..open.len().saturating_sub(err.input.len())).unwrap_or_default(), err.input, object, config) =>
+        {
+            let kind = if object { ErrorKind::UnterminatedObject } else { ErrorKind::UnterminatedArray };
+            nom::Err::Failure(InputError::new(open, kind))
+        }
+        e => e,
+    }
+}
+
+/// 检查数组或对象解析的结果，没有结束时报告 Unterminated 错误
+/// 
+/// input 是交给容器解析器的输入，可以带有前导空白；
+/// 开括号本身没有匹配上时，错误原样返回，其他解析器还可以继续尝试
+pub(crate) fn check_unterminated<'a, T>(result: PResult<'a, T>, input: &'a str, config: &ParserConfig) -> PResult<'a, T> {
+    result.map_err(|e| {
+        let open = ws(config)(input).map_or(input, |(open, _)| open);
+        if open.starts_with(['[', '{']) { unterminated_container(open, e, config) } else { e }
+    })
+}
+
+/// 开括号及其后的空白，并在进入新的一层之前检查嵌套层数
+///
+/// 与下面的 list_separator、close_container 一样，这些不递归的步骤放在单独的函数里，
+/// 它们的临时变量不会留在递归路径的栈帧中
+pub(crate) fn open_container<'a>(input: &'a str, config: &ParserConfig, open: char, depth: usize) -> PResult<'a, ()> {
+    let (rest, _) = delimited(ws(config), char_parser(open), ws(config)).parse(input)?;
+    check_depth(config, depth)(rest)
+}
+
+/// 元素或成员之间的逗号及其前后的空白
+pub(crate) fn list_separator<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    let (rest, _) = delimited(ws(config), char_parser(','), ws(config)).parse(input)?;
+    Ok((rest, ()))
+}
+
+/// 在元素列表之后接受一个可选的尾随逗号
+/// 
+/// 只有 allow_trailing_comma 打开并且列表非空时才接受，
+/// 因此 "[,]" 和 "{,}" 在任何模式下都不合法
+pub(crate) fn opt_trailing_comma<'a>(input: &'a str, config: &ParserConfig, empty: bool) -> PResult<'a, ()> {
+    if !config.allow_trailing_comma || empty {
+        return Ok((input, ()));
+    }
+    match list_separator(input, config) {
+        Err(nom::Err::Error(_)) => Ok((input, ())),  // 没有尾随逗号
+        result => result,
+    }
+}
+
+/// 闭括号及其后的空白，用 cut 锁定，失败时直接报告出错位置
+pub(crate) fn close_container<'a>(input: &'a str, config: &ParserConfig, close: char) -> PResult<'a, ()> {
+    let (rest, _) = cut(delimited(ws(config), char_parser(close), ws(config))).parse(input)?;
+    Ok((rest, ()))
+}
+
+/// 解析数组
+/// 处理由方括号包围的值列表
+/// 
+/// 匹配到开始方括号之后，结束方括号用 cut 锁定，
+/// 之后的失败直接报告出错位置，不再回退到其他分支
+/// 
+/// 元素个数超过 max_array_entries 时返回 ResourceLimit 错误，
+/// 到输入末尾都没有结束方括号时返回 UnterminatedArray，位置指向开始方括号
+pub(crate) fn parse_array<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    let result = parse_array_body(input, ctx, depth);
+    check_unterminated(result, input, ctx)
+}
+
+/// 数组的方括号和元素列表
+///
+/// 这个函数位于嵌套数组的递归路径上，因此不用组合子描述元素列表，而是手写循环，
+/// 让每一层的栈帧尽量小。规则与 separated_list0 相同：
+/// 逗号之后的元素解析失败（Error）时回退到逗号之前，由结束方括号报告错误
+fn parse_array_body<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    let (mut rest, ()) = open_container(input, ctx, '[', depth)?;
+    let entries = Cell::new(0);  // 这个数组已经解析出的元素个数
+    let mut items = Vec::new();
+    loop {
+        let start = if items.is_empty() {
+            rest
+        } else {
+            match list_separator(rest, ctx) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        match parse_primary(start, ctx, depth + 1) {  // 元素位于下一层
+            Ok((after, item)) => {
+                count_entry(ctx.max_array_entries, &entries, start)?;  // 检查 max_array_entries
+                items.push(item);
+                rest = after;
+            }
+            Err(nom::Err::Error(_)) => break,  // 不是值，回退到逗号之前
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, ctx, items.is_empty())?;
+    let (rest, ()) = close_container(rest, ctx, ']')?;  // 结束方括号
+    Ok((rest, V::array(items)))  // 将值列表转换为 JSON 数组
+}
+
+/// 解析对象的键，同时返回键在输入中的位置
+/// 位置用于在发现重复键时报告出错的地方
+fn parse_key_at<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, (&'a str, Cow<'a, str>)> {
+    let (rest, key) = parse_key(input, config)?;
+    Ok((rest, (input, key)))
+}
+
+/// 将键值对列表转换为 JSON 对象
+/// 
+/// 重复键按 config.duplicate_keys 处理：
+/// - LastWins：后出现的值覆盖先出现的值
+/// - FirstWins：保留先出现的值，忽略后面的
+/// - Error：返回 DuplicateKey 错误，位置指向第二次出现的键
+///
+/// 打开 sort_keys 时，先按键排序再插入；打开 intern_keys 时，键经过 ctx 中的缓存再插入
+fn build_object<'a, V: FromJson<'a>>(
+    mut pairs: Vec<((&'a str, Cow<'a, str>), V)>,
+    ctx: &Context<'_>,
+) -> Result<V, nom::Err<InputError<'a>>> {
+    if ctx.sort_keys {
+        pairs.sort_by(|((_, a), _), ((_, b), _)| a.cmp(b));  // 稳定排序，重复的键保持原来的先后
+    }
+    let mut map = V::new_map();
+    for ((position, k), value) in pairs {
+        if V::map_contains(&map, &k) {
+            match ctx.duplicate_keys {
+                DuplicateKeys::LastWins => {}  // 下面的 insert 会覆盖旧值
+                DuplicateKeys::FirstWins => continue,  // 保留旧值
+                DuplicateKeys::Error => {
+                    return Err(nom::Err::Failure(InputError::new(position, ErrorKind::DuplicateKey(k.into_owned()))));
+                }
+            }
+        }
+        match &ctx.keys {
+            Some(keys) => V::map_insert_interned(&mut map, k, value, keys),
+            None => V::map_insert(&mut map, k, value),
+        }
+    }
+    Ok(V::object(map))
+}
+
+/// 对象成员的键、前后的空白和冒号
+///
+/// 键计入 entries，超过 max_object_entries 时返回 Failure(ResourceLimit)；
+/// 键或冒号没有匹配上时返回 Error，由调用方回退
+fn parse_member_key<'a>(input: &'a str, ctx: &Context<'_>, entries: &Cell<usize>) -> PResult<'a, (&'a str, Cow<'a, str>)> {
+    let (rest, _) = ws(ctx)(input)?;
+    let (rest, key) = parse_key_at(rest, ctx)?;  // 键（字符串，json5 模式下也可以是标识符）
+    count_entry(ctx.max_object_entries, entries, key.0)?;  // 检查 max_object_entries
+    let (rest, _) = ws(ctx)(rest)?;
+    let (rest, _) = char_parser(':').parse(rest)?;  // 冒号分隔符
+    Ok((rest, key))
+}
+
+/// 解析对象
+/// 处理由大括号包围的键值对列表
+/// 
+/// 与数组相同，冒号之后的值和结束大括号都用 cut 锁定，
+/// 这样出错时报告的是真正出问题的位置
+/// 
+/// 成员个数超过 max_object_entries 时返回 ResourceLimit 错误，
+/// 到输入末尾都没有结束大括号时返回 UnterminatedObject，位置指向开始大括号
+pub(crate) fn parse_object<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    let result = parse_object_body(input, ctx, depth);
+    check_unterminated(result, input, ctx)
+}
+
+/// 对象的大括号和成员列表
+///
+/// 与 parse_array_body 一样手写循环，让递归路径上的栈帧尽量小
+fn parse_object_body<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    let (mut rest, ()) = open_container(input, ctx, '{', depth)?;
+    let entries = Cell::new(0);  // 这个对象已经解析出的成员个数
+    let mut pairs = Vec::new();
+    loop {
+        let start = if pairs.is_empty() {
+            rest
+        } else {
+            match list_separator(rest, ctx) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        let (after, key) = match parse_member_key(start, ctx, &entries) {
+            Ok(key) => key,
+            Err(nom::Err::Error(_)) => break,  // 不是成员，回退到逗号之前
+            Err(e) => return Err(e),
+        };
+        match parse_primary(after, ctx, depth + 1) {  // 值（可以是任何 JSON 值），位于下一层
+            Ok((after, value)) => {
+                pairs.push((key, value));
+                rest = after;
+            }
+            Err(nom::Err::Error(e)) => return Err(nom::Err::Failure(e)),  // 冒号之后必须是值，与 cut 相同
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, ctx, pairs.is_empty())?;
+    let object = build_object(pairs, ctx)?;  // 将键值对列表转换为 JSON 对象
+    let (rest, ()) = close_container(rest, ctx, '}')?;  // 结束大括号
+    Ok((rest, object))
+}
+
+/// 主解析函数
+/// 可以解析任何类型的 JSON 值
+/// 
+/// depth 表示当前值外层已有多少层数组/对象，顶层值为 0
+/// 
+/// 每成功解析出一个值（包括数组和对象本身），就在 ctx 中计数一次，
+/// 超过 max_elements 时返回 ResourceLimit 错误
+pub(crate) fn parse_primary<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    let (input, _) = ws(ctx)(input)?;  // 前导空白（以及注释）
+    let (rest, value) = match input.as_bytes().first() {
+        Some(b'[') => parse_array(input, ctx, depth)?,  // 容器直接分派，不经过 alt
+        Some(b'{') => parse_object(input, ctx, depth)?,
+        _ => parse_any(input, ctx, depth)?,
+    };
+    ctx.count_element(input)?;  // 统计已经解析出的值
+    ctx.record(input, depth);
+    let (rest, _) = ws(ctx)(rest)?;  // 尾随空白（以及注释）
+    Ok((rest, value))
+}
+
+/// 依次尝试每一种值的解析器
+fn parse_any<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>, depth: usize) -> PResult<'a, V> {
+    alt((  // 尝试以下解析器之一
+            parse_null,    // null 值
+            parse_bool,    // 布尔值
+            |i| parse_literal_no_case(i, ctx),  // 大小写不同的 true、false 和 null（需要 case_insensitive_literals）
+            |i| parse_non_finite(i, ctx),  // NaN 和无穷大（需要 allow_nan）
+            |i| parse_validated_number(i, ctx),  // 数字
+            map(|i| parse_string_with(i, ctx), V::string),  // 字符串
+            |i| parse_array(i, ctx, depth),   // 数组
+            |i| parse_object(i, ctx, depth),  // 对象
+    )).parse(input)
+}
+
+/// 检查文档不是空的
+/// 
+/// 输入在跳过空白（以及注释）之后什么都没有时返回 Failure(EmptyInput)，
+/// 出错位置在输入的末尾；否则不消耗任何输入
+pub(crate) fn reject_empty<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    let (rest, _) = ws(config)(input)?;
+    if rest.is_empty() {
+        return Err(nom::Err::Failure(InputError::new(rest, ErrorKind::EmptyInput)));
+    }
+    Ok((input, ()))
+}
+
+/// 检查输入没有超过 max_input_bytes
+/// 
+/// 超过时返回 Failure(ResourceLimit)，位置指向输入的开头，还没有做任何解析；
+/// 否则不消耗任何输入
+pub(crate) fn check_input_size<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    if config.max_input_bytes.is_some_and(|max| input.len() > max) {
+        return Err(nom::Err::Failure(InputError::new(input, ErrorKind::ResourceLimit)));
+    }
+    Ok((input, ()))
+}
+
+/// 打开 require_root_container 时，检查顶层的值以 '[' 或 '{' 开头
+/// 
+/// 顶层是标量时返回 Failure(RootNotContainer)，位置指向这个标量；否则不消耗任何输入。
+/// 空输入留给调用方按各自的规则报错
+pub(crate) fn check_root<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    if !config.require_root_container {
+        return Ok((input, ()));
+    }
+    let (rest, _) = ws(config)(input)?;
+    if !rest.is_empty() && !rest.starts_with(['[', '{']) {
+        return Err(nom::Err::Failure(InputError::new(rest, ErrorKind::RootNotContainer)));
+    }
+    Ok((input, ()))
+}
+
+/// 解析完整的 JSON 文档
+/// 在 parse_primary 外面套一层 all_consuming，
+/// 值后面除了空白（已被 parse_primary 吃掉）不允许再有任何内容
+/// 
+/// 举例：
+/// - 输入 " 123 " -> 成功
+/// - 输入 "123garbage" -> 失败，存在尾随数据
+/// - 输入 "{} {}" -> 失败，第二个对象属于尾随数据
+/// - 输入 ""、"   "、"\n\n" -> 失败，返回 EmptyInput；打开 empty_as_null 时成功，返回 null
+pub(crate) fn parse_document<'a, V: FromJson<'a>>(input: &'a str, config: &ParserConfig) -> PResult<'a, V> {
+    parse_document_in(input, &Context::new(config))
+}
+
+/// 与 parse_document 相同，但使用调用方提供的 ctx，解析结束后可以从中读取统计信息
+pub(crate) fn parse_document_in<'a, V: FromJson<'a>>(input: &'a str, ctx: &Context<'_>) -> PResult<'a, V> {
+    check_input_size(input, ctx)?;  // 输入太长时什么都不做
+    if ctx.empty_as_null {
+        let (rest, _) = ws(ctx)(input)?;
+        if rest.is_empty() {
+            return Ok((rest, V::null()));  // 空输入当作 null
+        }
+    }
+    reject_empty(input, ctx)?;
+    check_root(input, ctx)?;  // 顶层必须是容器时，标量在这里报错
+    all_consuming(|i| parse_primary(i, ctx, 0)).parse(input)
+}
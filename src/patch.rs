@@ -0,0 +1,222 @@
+//! JSON Patch
+//!
+//! 按 RFC 6902 把一组编辑操作应用到文档上。
+//! 与 merge 模块的 RFC 7386 不同，每个操作都用 JSON Pointer 指定位置，
+//! 可以在数组中间插入元素、移动和复制值，以及检查某处的值。
+
+use core::fmt;
+
+use serde_json::{Map, Number, Value};
+
+use crate::pointer::{parse_index, split_pointer};
+
+/// 应用 JSON Patch 时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// 补丁本身格式不对，例如不是数组、缺少 op 成员或者 op 未知，附带原因
+    InvalidOperation(String),
+    /// path 或 from 不是合法的 JSON Pointer，附带该指针
+    InvalidPointer(String),
+    /// 指针指向的位置不存在，附带该指针
+    PathNotFound(String),
+    /// test 操作比较的值不相等，附带被检查的指针
+    TestFailed(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::InvalidOperation(reason) => write!(f, "无效的补丁操作：{}", reason),
+            PatchError::InvalidPointer(ptr) => write!(f, "无效的 JSON Pointer：{:?}", ptr),
+            PatchError::PathNotFound(ptr) => write!(f, "路径不存在：{:?}", ptr),
+            PatchError::TestFailed(ptr) => write!(f, "test 操作失败，{:?} 处的值不相等", ptr),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+/// 按 RFC 6902 把 patch 应用到 doc 上
+///
+/// patch 必须是操作对象组成的数组，每个操作都有 op 和 path 成员：
+/// - add：在 path 处加入 value。目标是对象时插入或覆盖键，
+///   目标是数组时在下标处插入，下标 "-" 表示追加到末尾
+/// - remove：删除 path 处的值
+/// - replace：把 path 处已有的值替换为 value
+/// - move：把 from 处的值移动到 path，from 不能是 path 的上级
+/// - copy：把 from 处的值复制到 path
+/// - test：检查 path 处的值等于 value，不相等时返回 TestFailed。
+///   按 RFC 6902 第 4.6 节比较：数字按数值比较（1 和 1.0 相等），数组和对象逐个成员比较
+///
+/// 操作依次执行。任何一个操作失败时返回错误，doc 保持原样，不会只应用一半。
+///
+/// 举例：对于 {"a": [1, 2]}
+/// - [{"op": "add", "path": "/a/1", "value": 9}] -> {"a": [1, 9, 2]}
+/// - [{"op": "move", "from": "/a", "path": "/b"}] -> {"b": [1, 2]}
+/// - [{"op": "test", "path": "/a/0", "value": 5}] -> 失败，返回 TestFailed
+/// - [{"op": "remove", "path": "/x"}] -> 失败，返回 PathNotFound
+pub fn apply_patch(doc: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let Value::Array(operations) = patch else {
+        return Err(PatchError::InvalidOperation("补丁必须是数组".to_string()));
+    };
+    let mut result = doc.clone();  // 在副本上修改，全部成功后再替换
+    for operation in operations {
+        apply_operation(&mut result, operation)?;
+    }
+    *doc = result;
+    Ok(())
+}
+
+/// 执行单个操作
+fn apply_operation(doc: &mut Value, operation: &Value) -> Result<(), PatchError> {
+    let Value::Object(members) = operation else {
+        return Err(PatchError::InvalidOperation("操作必须是对象".to_string()));
+    };
+    let op = string_member(members, "op")?;
+    let path = string_member(members, "path")?;
+    match op {
+        "add" => add(doc, path, value_member(members)?.clone()),
+        "remove" => remove(doc, path).map(|_| ()),
+        "replace" => {
+            *lookup(doc, path)? = value_member(members)?.clone();  // 目标必须已经存在
+            Ok(())
+        }
+        "move" => {
+            let from = string_member(members, "from")?;
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(PatchError::InvalidOperation("不能把值移动到它自己的子节点里".to_string()));
+            }
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        "copy" => {
+            let from = string_member(members, "from")?;
+            let value = lookup(doc, from)?.clone();
+            add(doc, path, value)
+        }
+        "test" => {
+            if json_equal(lookup(doc, path)?, value_member(members)?) {
+                Ok(())
+            } else {
+                Err(PatchError::TestFailed(path.to_string()))
+            }
+        }
+        _ => Err(PatchError::InvalidOperation(format!("未知的操作 {:?}", op))),
+    }
+}
+
+/// test 操作使用的相等比较
+///
+/// 与 serde_json::Value 的 == 不同，整数和浮点数按数值比较；
+/// 数组要求长度相同且对应元素相等，对象要求键的集合相同且对应的值相等，与成员顺序无关。
+fn json_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => numbers_equal(x, y),
+        (Value::Array(x), Value::Array(y)) => x.len() == y.len() && x.iter().zip(y).all(|(x, y)| json_equal(x, y)),
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len() && x.iter().all(|(key, x)| y.get(key).is_some_and(|y| json_equal(x, y)))
+        }
+        _ => a == b,  // 其他标量，或者类型不同
+    }
+}
+
+/// 按数值比较两个数字
+///
+/// 两边都能精确表示为整数时按整数比较，避免大整数转换为 f64 时丢失精度；
+/// 否则按 f64 比较。
+fn numbers_equal(a: &Number, b: &Number) -> bool {
+    match (exact_integer(a), exact_integer(b)) {
+        (Some(x), Some(y)) => x == y,
+        (None, None) => a.as_f64() == b.as_f64(),
+        _ => false,  // 一边是整数，另一边是有小数部分的数
+    }
+}
+
+/// 数字恰好是整数时返回这个整数
+/// 浮点数的小数部分为 0 且在 i128 范围内时也算，例如 1.0 和 1e3
+fn exact_integer(n: &Number) -> Option<i128> {
+    n.as_i64()
+        .map(i128::from)
+        .or_else(|| n.as_u64().map(i128::from))
+        .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0 && f.abs() < 1e38).map(|f| f as i128))
+}
+
+/// 读取操作中字符串类型的成员，如 op、path、from
+fn string_member<'a>(members: &'a Map<String, Value>, name: &str) -> Result<&'a str, PatchError> {
+    match members.get(name) {
+        Some(Value::String(s)) => Ok(s),
+        Some(_) => Err(PatchError::InvalidOperation(format!("{} 必须是字符串", name))),
+        None => Err(PatchError::InvalidOperation(format!("缺少 {} 成员", name))),
+    }
+}
+
+/// 读取操作中的 value 成员，它可以是任意值，包括 null
+fn value_member(members: &Map<String, Value>) -> Result<&Value, PatchError> {
+    members.get("value").ok_or_else(|| PatchError::InvalidOperation("缺少 value 成员".to_string()))
+}
+
+/// 把指针拆分为父节点的路径和最后一个引用片段
+/// 空指针没有父节点，返回 None
+fn split_last(ptr: &str) -> Result<Option<(Vec<String>, String)>, PatchError> {
+    let mut tokens = split_pointer(ptr).ok_or_else(|| PatchError::InvalidPointer(ptr.to_string()))?;
+    Ok(tokens.pop().map(|last| (tokens, last)))
+}
+
+/// 沿着引用片段找到目标值的可变引用
+fn walk<'a>(doc: &'a mut Value, tokens: &[String], ptr: &str) -> Result<&'a mut Value, PatchError> {
+    let mut current = doc;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map.get_mut(token),
+            Value::Array(items) => parse_index(token).and_then(|i| items.get_mut(i)),
+            _ => None,  // 标量没有子节点
+        }
+        .ok_or_else(|| PatchError::PathNotFound(ptr.to_string()))?;
+    }
+    Ok(current)
+}
+
+/// 找到 ptr 指向的已有值
+fn lookup<'a>(doc: &'a mut Value, ptr: &str) -> Result<&'a mut Value, PatchError> {
+    let tokens = split_pointer(ptr).ok_or_else(|| PatchError::InvalidPointer(ptr.to_string()))?;
+    walk(doc, &tokens, ptr)
+}
+
+/// 在 ptr 处加入 value
+/// 父节点必须存在；空指针表示替换整个文档
+fn add(doc: &mut Value, ptr: &str, value: Value) -> Result<(), PatchError> {
+    let Some((parent, last)) = split_last(ptr)? else {
+        *doc = value;  // 替换整个文档
+        return Ok(());
+    };
+    match walk(doc, &parent, ptr)? {
+        Value::Object(map) => {
+            map.insert(last, value);  // 插入新键或覆盖已有的键
+        }
+        Value::Array(items) => {
+            let index = if last == "-" {
+                items.len()  // 追加到末尾
+            } else {
+                parse_index(&last).filter(|&i| i <= items.len()).ok_or_else(|| PatchError::PathNotFound(ptr.to_string()))?
+            };
+            items.insert(index, value);
+        }
+        _ => return Err(PatchError::PathNotFound(ptr.to_string())),  // 标量不能加入子节点
+    }
+    Ok(())
+}
+
+/// 删除 ptr 处的值并返回它
+/// 整个文档不能被删除
+fn remove(doc: &mut Value, ptr: &str) -> Result<Value, PatchError> {
+    let Some((parent, last)) = split_last(ptr)? else {
+        return Err(PatchError::InvalidOperation("不能删除整个文档".to_string()));
+    };
+    let removed = match walk(doc, &parent, ptr)? {
+        Value::Object(map) => map.shift_remove(&last),  // 其余成员保持原来的顺序
+        Value::Array(items) => parse_index(&last).filter(|&i| i < items.len()).map(|i| items.remove(i)),
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::PathNotFound(ptr.to_string()))
+}
@@ -0,0 +1,60 @@
+//! 按路径片段访问嵌套的值
+//!
+//! 与 JSON Pointer 字符串相比，路径直接由键和下标组成，
+//! 程序里构造路径时不需要拼接字符串，也不需要处理 '~' 和 '/' 的转义。
+
+use serde_json::Value;
+
+/// 路径中的一段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathSeg<'a> {
+    /// 对象的键
+    Key(&'a str),
+    /// 数组的下标
+    Index(usize),
+}
+
+impl<'a> From<&'a str> for PathSeg<'a> {
+    fn from(key: &'a str) -> Self {
+        PathSeg::Key(key)
+    }
+}
+
+impl From<usize> for PathSeg<'_> {
+    fn from(index: usize) -> Self {
+        PathSeg::Index(index)
+    }
+}
+
+/// 按路径查找嵌套的值
+///
+/// 从 value 开始依次应用每一段：Key 只能用于对象，Index 只能用于数组。
+/// 空路径指向 value 本身。
+/// 键不存在、下标越界，或者片段与当前值的类型不符时返回 None。
+///
+/// 举例：对于 {"courses": ["Math", "Science"], "address": {"city": "New York"}}
+/// - get(&v, &[PathSeg::Key("address"), PathSeg::Key("city")]) -> "New York"
+/// - get(&v, &[PathSeg::Key("courses"), PathSeg::Index(1)]) -> "Science"
+/// - get(&v, &[PathSeg::Key("courses"), PathSeg::Key("0")]) -> None，数组不能用键访问
+/// - get(&v, &[]) -> 整个文档
+pub fn get<'a>(value: &'a Value, path: &[PathSeg<'_>]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |current, seg| match (seg, current) {
+        (PathSeg::Key(key), Value::Object(map)) => map.get(*key),
+        (PathSeg::Index(index), Value::Array(items)) => items.get(*index),
+        _ => None,  // 类型不符，或者当前值是标量
+    })
+}
+
+/// 按路径查找嵌套的值，返回可变引用
+///
+/// 规则与 get 相同，可以用来原地修改文档中的某个值。
+///
+/// 举例：
+/// - *get_mut(&mut v, &[PathSeg::Key("age")])? = 31.into() -> 把 age 改为 31
+pub fn get_mut<'a>(value: &'a mut Value, path: &[PathSeg<'_>]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |current, seg| match (seg, current) {
+        (PathSeg::Key(key), Value::Object(map)) => map.get_mut(*key),
+        (PathSeg::Index(index), Value::Array(items)) => items.get_mut(*index),
+        _ => None,  // 类型不符，或者当前值是标量
+    })
+}
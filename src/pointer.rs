@@ -0,0 +1,120 @@
+//! JSON Pointer
+//!
+//! 按 RFC 6901 在解析出的 Value 中定位嵌套的值，例如 "/address/city"。
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::Value;
+
+/// 按 JSON Pointer 查找嵌套的值
+///
+/// 详细解释：
+/// 1. 空字符串指向整个文档，其他指针必须以 '/' 开头
+/// 2. 指针按 '/' 分成若干个引用片段，每个片段中：
+///    - "~1" 表示 '/'，"~0" 表示 '~'
+///    - '~' 后面跟其他字符（或者位于末尾）是无效的指针
+/// 3. 遇到对象时，片段是键名；遇到数组时，片段必须是十进制下标：
+///    - "0" 或者不以 0 开头的数字，"01" 这样的前导零无效
+///    - "-" 表示数组末尾之后的位置，查找时总是不存在
+///
+/// 指针无效、路径不存在或下标越界时都返回 None
+///
+/// 举例：对于 {"a/b": 1, "m~n": 2, "list": [10, 20]}
+/// - pointer(&v, "") -> 整个文档
+/// - pointer(&v, "/a~1b") -> 1
+/// - pointer(&v, "/m~0n") -> 2
+/// - pointer(&v, "/list/1") -> 20
+/// - pointer(&v, "/list/2") -> None，下标越界
+/// - pointer(&v, "list") -> None，不以 '/' 开头
+pub fn pointer<'a>(value: &'a Value, ptr: &str) -> Option<&'a Value> {
+    if ptr.is_empty() {
+        return Some(value);  // 空指针指向整个文档
+    }
+    let tokens = ptr.strip_prefix('/')?;  // 必须以 '/' 开头
+    let mut current = value;
+    for token in tokens.split('/') {
+        let token = unescape_token(token)?;
+        current = match current {
+            Value::Object(map) => map.get(&token)?,
+            Value::Array(items) => items.get(parse_index(&token)?)?,  // 越界时返回 None
+            _ => return None,  // 标量没有子节点
+        };
+    }
+    Some(current)
+}
+
+/// 判断 JSON Pointer 是否指向一个存在的值
+///
+/// 与 pointer(value, ptr).is_some() 相同，检查功能开关之类的场景读起来更直接。
+/// 指向的值是 null 时也返回 true，只有指针无效或路径不存在时返回 false
+///
+/// 举例：对于 {"flags": {"beta": null}}
+/// - has_pointer(&v, "/flags/beta") -> true
+/// - has_pointer(&v, "/flags/alpha") -> false
+/// - has_pointer(&v, "flags") -> false，不以 '/' 开头
+pub fn has_pointer(value: &Value, ptr: &str) -> bool {
+    pointer(value, ptr).is_some()
+}
+
+/// 列出 JSON Pointer 指向的对象的所有键
+///
+/// 键的顺序与对象中的顺序相同，也就是它们在文档中出现的顺序。
+/// 指针无效、路径不存在或者指向的不是对象时返回 None，空对象返回空列表
+///
+/// 举例：对于 {"a": {"x": 1, "y": 2}, "b": [1]}
+/// - keys_at(&v, "/a") -> Some(["x", "y"])
+/// - keys_at(&v, "") -> Some(["a", "b"])
+/// - keys_at(&v, "/b") -> None，数组没有键
+pub fn keys_at<'a>(value: &'a Value, ptr: &str) -> Option<Vec<&'a str>> {
+    match pointer(value, ptr)? {
+        Value::Object(map) => Some(map.keys().map(String::as_str).collect()),
+        _ => None,
+    }
+}
+
+/// 把指针拆分为还原后的引用片段
+/// 空指针得到空列表，指针无效时返回 None
+pub(crate) fn split_pointer(ptr: &str) -> Option<Vec<String>> {
+    if ptr.is_empty() {
+        return Some(Vec::new());  // 空指针指向整个文档
+    }
+    ptr.strip_prefix('/')?.split('/').map(unescape_token).collect()
+}
+
+/// 还原引用片段中的 "~1" 和 "~0"
+/// '~' 后面不是 '0' 或 '1' 时返回 None
+fn unescape_token(token: &str) -> Option<String> {
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next()? {
+                '0' => out.push('~'),
+                '1' => out.push('/'),
+                _ => return None,  // 无效的转义
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// 把键名转义为引用片段，是 unescape_token 的逆操作
+/// 先把 '~' 换成 "~0"，再把 '/' 换成 "~1"
+pub(crate) fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// 把引用片段解析为数组下标
+/// 只接受 "0" 或不以 0 开头的十进制数字
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;  // 包括 "-" 和带符号的数字
+    }
+    if token.len() > 1 && token.starts_with('0') {
+        return None;  // 前导零
+    }
+    token.parse().ok()
+}
@@ -0,0 +1,47 @@
+//! 清理 null 成员
+//!
+//! 发送 API 请求之前，经常需要去掉值为 null 的字段，
+//! 有时也要顺便去掉空数组和空对象，让载荷只包含真正有内容的部分。
+
+use serde_json::Value;
+
+/// 递归删除对象中值为 null 的成员
+///
+/// 详细解释：
+/// 1. 先递归处理每个成员和元素，再检查成员本身，
+///    所以清理之后变成空容器的成员也能在同一次调用中被删除
+/// 2. 对象中值为 null 的成员总是被删除
+/// 3. remove_empty 为 true 时，值为空数组或空对象的成员也被删除
+/// 4. 数组中的 null 和空容器保留不动，删除元素会改变后面元素的下标
+/// 5. value 本身即使是 null 或空容器也不会被改变，只处理它内部的成员
+///
+/// 举例：
+/// - {"a": null, "b": 1} -> {"b": 1}
+/// - {"a": {"b": null}}，remove_empty 为 false -> {"a": {}}
+/// - {"a": {"b": null}}，remove_empty 为 true -> {}
+/// - [null, {"a": null}] -> [null, {}]
+pub fn prune_nulls(value: &mut Value, remove_empty: bool) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                prune_nulls(item, remove_empty);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                prune_nulls(item, remove_empty);  // 先清理内部，成员可能因此变成空容器
+            }
+            map.retain(|_, item| !(item.is_null() || (remove_empty && is_empty_container(item))));
+        }
+        _ => {}
+    }
+}
+
+/// 判断是不是空数组或空对象
+fn is_empty_container(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
@@ -0,0 +1,77 @@
+//! 从 Read 解析
+//!
+//! 让调用方直接把文件、网络连接等 std::io::Read 交给解析器，
+//! 不需要自己先把内容读成字符串。
+//! 打开 tokio 特性后，还可以从 tokio 的 AsyncRead 读取。
+
+use std::io::Read;
+
+use serde_json::Value;
+
+use crate::{ErrorKind, ParseError, parse_iterative};
+
+/// 从 reader 读取 JSON 文本并解析
+///
+/// 缓冲策略：
+/// 1. 用 read_to_end 把 reader 的全部内容读进一个 Vec<u8>，
+///    缓冲区随读取按倍数增长，reader 本身不需要再包一层 BufReader
+/// 2. 在缓冲区上原地校验 UTF-8，不会再复制一份字符串
+/// 3. 用非递归解析器（parse_iterative）解析，嵌套再深也不会增加调用栈，
+///    适合来源不可信的大文件
+///
+/// 因此峰值内存大约是输入的字节数加上解析结果的大小。
+/// 解析器需要完整的输入才能开始工作，这里不会边读边解析；
+/// 每行一个值的大文件可以逐行读取后交给 parse_lines，内存只和最长的一行有关。
+///
+/// 错误处理：
+/// - 读取失败时返回 Io 错误，位置指向已经读到的内容的末尾
+/// - 内容不是合法的 UTF-8 时返回 InvalidUtf8，与 parse_bytes 相同
+/// - 其余错误与 parse_iterative 相同
+///
+/// 举例：
+/// - from_reader(File::open("data.json")?) -> 解析整个文件
+/// - from_reader(&b"[1, 2]"[..]) -> 成功，&[u8] 也实现了 Read
+pub fn from_reader<R: Read>(mut reader: R) -> Result<Value, ParseError> {
+    let mut buf = Vec::new();
+    match reader.read_to_end(&mut buf) {
+        Ok(_) => parse_buffer(&buf),
+        Err(e) => Err(io_error(&buf, e)),
+    }
+}
+
+/// 从异步的 reader 读取 JSON 文本并解析
+///
+/// 与 from_reader 相同，只是读取过程是异步的：
+/// 先把全部内容读进缓冲区，再一次性解析，错误的类别和位置也与 from_reader 相同。
+/// 解析本身是同步的 CPU 计算，特别大的输入可以考虑放到 spawn_blocking 里。
+///
+/// 需要打开 tokio 特性。
+///
+/// 举例：
+/// - from_async_reader(tokio::fs::File::open("data.json").await?).await -> 解析整个文件
+/// - from_async_reader(&b"{}"[..]).await -> 成功
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R: tokio::io::AsyncRead + Unpin>(mut reader: R) -> Result<Value, ParseError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buf = Vec::new();
+    match reader.read_to_end(&mut buf).await {
+        Ok(_) => parse_buffer(&buf),
+        Err(e) => Err(io_error(&buf, e)),
+    }
+}
+
+/// 校验缓冲区是否为合法的 UTF-8，再用非递归解析器解析
+fn parse_buffer(buf: &[u8]) -> Result<Value, ParseError> {
+    let text = std::str::from_utf8(buf).map_err(|e| ParseError::invalid_utf8(buf, e))?;
+    parse_iterative(text)
+}
+
+/// 构造读取失败的错误，出错位置是已读内容的末尾
+fn io_error(buf: &[u8], err: std::io::Error) -> ParseError {
+    let valid = match std::str::from_utf8(buf) {
+        Ok(text) => text,
+        Err(utf8) => std::str::from_utf8(&buf[..utf8.valid_up_to()]).unwrap_or_default(),  // 合法的前缀
+    };
+    ParseError::new(valid, "", ErrorKind::Io(err.to_string()))
+}
@@ -0,0 +1,193 @@
+//! 简单的结构校验
+//!
+//! 不引入完整的 JSON Schema 实现，只回答最常见的问题：
+//! 这个文档有没有必需的字段，各个字段的类型对不对。
+//! 模式语言有意保持很小：只有 Type 中的几种类型、对象的字段列表和数组的元素类型，
+//! 没有取值范围、正则表达式、枚举值和引用。
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::access::{TypeError, type_name};
+use crate::pointer::escape_token;
+
+/// 值需要满足的类型
+///
+/// 举例：{"name": 字符串, "tags": 字符串数组, 可选的 "age": 整数}
+///
+/// ```
+/// use json_parser::{Field, Type};
+///
+/// let schema = Type::Object {
+///     fields: vec![
+///         Field::required("name", Type::String),
+///         Field::required("tags", Type::Array { items: Box::new(Type::String) }),
+///         Field::optional("age", Type::Integer),
+///     ],
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// 任意值，不做检查
+    Any,
+    /// null
+    Null,
+    /// 布尔值
+    Bool,
+    /// 任意数字
+    Number,
+    /// 可以用 i64 或 u64 表示的整数，1.0 这样的浮点数不算
+    Integer,
+    /// 字符串
+    String,
+    /// 每个元素都满足 items 的数组，空数组总是满足
+    Array {
+        /// 元素的类型
+        items: Box<Type>,
+    },
+    /// 对象，fields 中列出的字段按各自的类型检查
+    ///
+    /// 没有列出的字段不做检查，也不算错误
+    Object {
+        /// 需要检查的字段
+        fields: Vec<Field>,
+    },
+}
+
+/// 模式就是根节点需要满足的 Type
+pub type Schema = Type;
+
+/// 对象中的一个字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// 字段的键
+    pub name: String,
+    /// 字段的值需要满足的类型
+    pub ty: Type,
+    /// 是否必须出现；可选的字段不存在时不检查，存在时仍要满足 ty
+    pub required: bool,
+}
+
+impl Field {
+    /// 必须出现的字段
+    pub fn required(name: impl Into<String>, ty: Type) -> Self {
+        Field { name: name.into(), ty, required: true }
+    }
+
+    /// 可以不出现的字段
+    pub fn optional(name: impl Into<String>, ty: Type) -> Self {
+        Field { name: name.into(), ty, required: false }
+    }
+}
+
+/// 值不满足模式的一处地方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// 出错位置的 JSON Pointer，根节点为空字符串；字段缺失时是所在对象的位置
+    pub path: String,
+    /// 出错的原因
+    pub kind: SchemaErrorKind,
+}
+
+/// 不满足模式的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaErrorKind {
+    /// 值的类型不对，类型名与 as_str_required 等函数的 TypeError 相同
+    Type(TypeError),
+    /// 对象缺少必需的字段，附带字段的键
+    MissingField(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SchemaErrorKind::Type(e) => write!(f, "位置 {:?}：{}", self.path, e),
+            SchemaErrorKind::MissingField(name) => write!(f, "位置 {:?}：缺少必需的字段 {:?}", self.path, name),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// 检查 value 是否满足 schema
+///
+/// 详细解释：
+/// 1. 标量类型只检查值的类型是否一致
+/// 2. Array 逐个检查元素，Object 按 fields 的顺序检查字段：
+///    必需的字段不存在时记录 MissingField，存在的字段递归检查
+/// 3. 类型不一致时记录 Type 错误，不再检查这个值的内部
+///
+/// 不会在第一处错误就停下，而是检查完整个文档，按文档中的顺序返回所有错误。
+///
+/// 举例：
+///
+/// ```
+/// use json_parser::{Field, SchemaErrorKind, Type, parse, validate_schema};
+///
+/// let schema = Type::Object {
+///     fields: vec![
+///         Field::required("id", Type::Integer),
+///         Field::required("name", Type::String),
+///         Field::optional("tags", Type::Array { items: Box::new(Type::String) }),
+///     ],
+/// };
+/// assert!(validate_schema(&parse(r#"{"id": 1, "name": "a", "extra": null}"#).unwrap(), &schema).is_ok());
+///
+/// let errors = validate_schema(&parse(r#"{"id": "1", "tags": ["x", 2]}"#).unwrap(), &schema).unwrap_err();
+/// let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+/// assert_eq!(paths, ["/id", "", "/tags/1"]);
+/// assert_eq!(errors[1].kind, SchemaErrorKind::MissingField("name".to_string()));
+/// ```
+pub fn validate_schema(value: &Value, schema: &Schema) -> Result<(), Vec<SchemaError>> {
+    let mut errors = Vec::new();
+    let mut path = String::new();  // 当前位置的 JSON Pointer，随递归增减
+    check(&mut errors, &mut path, value, schema);
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// 检查 path 处的 value，把错误追加到 errors 末尾
+fn check(errors: &mut Vec<SchemaError>, path: &mut String, value: &Value, ty: &Type) {
+    let expected = match (ty, value) {
+        (Type::Any, _) | (Type::Null, Value::Null) | (Type::Bool, Value::Bool(_)) => return,
+        (Type::Number, Value::Number(_)) | (Type::String, Value::String(_)) => return,
+        (Type::Integer, Value::Number(n)) if n.is_i64() || n.is_u64() => return,
+        (Type::Array { items }, Value::Array(values)) => {
+            for (index, item) in values.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{}", index));
+                check(errors, path, item, items);
+                path.truncate(len);  // 回到当前数组
+            }
+            return;
+        }
+        (Type::Object { fields }, Value::Object(map)) => {
+            for field in fields {
+                match map.get(&field.name) {
+                    Some(member) => {
+                        let len = path.len();
+                        path.push('/');
+                        path.push_str(&escape_token(&field.name));
+                        check(errors, path, member, &field.ty);
+                        path.truncate(len);  // 回到当前对象
+                    }
+                    None if field.required => errors.push(SchemaError {
+                        path: path.clone(),
+                        kind: SchemaErrorKind::MissingField(field.name.clone()),
+                    }),
+                    None => {}  // 可选的字段不存在
+                }
+            }
+            return;
+        }
+        (Type::Null, _) => "null",
+        (Type::Bool, _) => "bool",
+        (Type::Number, _) => "number",
+        (Type::Integer, _) => "integer",
+        (Type::String, _) => "string",
+        (Type::Array { .. }, _) => "array",
+        (Type::Object { .. }, _) => "object",
+    };
+    let kind = SchemaErrorKind::Type(TypeError { expected, actual: type_name(value) });
+    errors.push(SchemaError { path: path.clone(), kind });
+}
@@ -0,0 +1,346 @@
+//! 序列化
+//!
+//! 把 serde_json::Value 重新输出为 JSON 文本，
+//! 输出结果可以被 parse 原样解析回来。
+
+use serde_json::Value;
+
+/// 将 JSON 值输出为紧凑的 JSON 文本
+///
+/// 输出中不包含任何多余的空白，字符串中的引号、反斜杠和控制字符都会被转义。
+///
+/// 举例：
+/// - 输入 {"a": [1, 2]} -> 输出 "{\"a\":[1,2]}"
+/// - 输入字符串 "a\nb" -> 输出 "\"a\\nb\""
+///
+/// 对任意 v 都有 parse(&to_string(&v)) == Ok(v)
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, false);
+    out
+}
+
+/// 将 JSON 值追加到 out 末尾
+/// ascii_only 为 true 时字符串中的非 ASCII 字符也转义，见 write_string_with
+fn write_value(out: &mut String, value: &Value, ascii_only: bool) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&n.to_string()),  // serde_json 的数字文本本身就是合法的 JSON
+        Value::String(s) => write_string_with(out, s, ascii_only),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 元素之间用逗号分隔
+                }
+                write_value(out, item, ascii_only);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 成员之间用逗号分隔
+                }
+                write_string_with(out, key, ascii_only);
+                out.push(':');
+                write_value(out, item, ascii_only);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// 缩进格式的输出选项，见 to_string_pretty_with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyConfig {
+    /// 每一层缩进使用的字符串，例如 "  "、"    " 或 "\t"
+    ///
+    /// 不检查内容，传入空白以外的字符会使输出不再是合法的 JSON。
+    /// 默认为两个空格。
+    pub indent: String,
+
+    /// 一行最多多少个字符，用来把较短的数组压缩到一行
+    ///
+    /// 设置之后，数组整体写成一行（如 [1, 2, 3]）时，如果加上这一行前面已有的缩进和键
+    /// 仍不超过这个宽度，就输出为一行，否则照常每个元素占一行。
+    /// 宽度按字符计数，制表符也算一个字符；对象总是每个成员占一行。
+    /// 默认为 None，不压缩。
+    pub max_width: Option<usize>,
+
+    /// 是否把字符串中的非 ASCII 字符都写成 \uXXXX 转义
+    ///
+    /// 用于不能正确处理 UTF-8 的下游系统，输出只含 ASCII 字符。
+    /// 基本多文种平面之外的字符写成 UTF-16 代理对，例如 😀 写成 \ud83d\ude00。
+    /// 解析时两种写法得到相同的字符串。默认关闭，直接输出 UTF-8。
+    pub ascii_only: bool,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        PrettyConfig {
+            indent: "  ".to_string(),
+            max_width: None,
+            ascii_only: false,
+        }
+    }
+}
+
+/// 将 JSON 值输出为便于阅读的 JSON 文本
+///
+/// 每一层缩进 indent 个空格，逗号之后换行，冒号之后加一个空格。
+/// 空数组和空对象输出为单行的 [] 和 {}，不会带空的缩进内容。
+/// 需要用制表符缩进或者压缩短数组时使用 to_string_pretty_with。
+///
+/// 举例：indent 为 2 时，输入 {"a": [1, 2], "b": {}} 输出为
+///
+/// ```text
+/// {
+///   "a": [
+///     1,
+///     2
+///   ],
+///   "b": {}
+/// }
+/// ```
+pub fn to_string_pretty(value: &Value, indent: usize) -> String {
+    let config = PrettyConfig { indent: " ".repeat(indent), ..PrettyConfig::default() };
+    to_string_pretty_with(value, &config)
+}
+
+/// 按 config 将 JSON 值输出为便于阅读的 JSON 文本
+///
+/// 规则与 to_string_pretty 相同，只是每一层的缩进是 config.indent，
+/// 可以按 config.max_width 把较短的数组压缩到一行，
+/// 还可以用 config.ascii_only 把非 ASCII 字符都写成转义。
+///
+/// 举例：indent 为 "  "（默认）、max_width 为 20 时，输入 {"a": [1, 2], "b": [[1, 2], ["long string"]]} 输出为
+///
+/// ```text
+/// {
+///   "a": [1, 2],
+///   "b": [
+///     [1, 2],
+///     ["long string"]
+///   ]
+/// }
+/// ```
+///
+/// indent 为 "\t" 时每一层缩进一个制表符。
+pub fn to_string_pretty_with(value: &Value, config: &PrettyConfig) -> String {
+    let mut out = String::new();
+    write_pretty(&mut out, value, config, 0);
+    out
+}
+
+/// 将 JSON 值以缩进格式追加到 out 末尾
+/// level 是当前所在的层数，决定换行后的缩进宽度
+fn write_pretty(out: &mut String, value: &Value, config: &PrettyConfig, level: usize) {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            if let Some(max_width) = config.max_width {
+                let mut line = String::new();
+                write_single_line(&mut line, value, config.ascii_only);
+                let column = out.rsplit('\n').next().unwrap_or_default().chars().count();  // 这一行已有的字符数
+                if column + line.chars().count() <= max_width {
+                    out.push_str(&line);  // 整个数组放得进一行
+                    return;
+                }
+            }
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 元素之间用逗号分隔
+                }
+                write_newline(out, config, level + 1);  // 每个元素独占一行
+                write_pretty(out, item, config, level + 1);
+            }
+            write_newline(out, config, level);  // 结束方括号回到当前缩进
+            out.push(']');
+        }
+        Value::Object(map) if !map.is_empty() => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 成员之间用逗号分隔
+                }
+                write_newline(out, config, level + 1);  // 每个成员独占一行
+                write_string_with(out, key, config.ascii_only);
+                out.push_str(": ");  // 冒号之后加一个空格
+                write_pretty(out, item, config, level + 1);
+            }
+            write_newline(out, config, level);  // 结束大括号回到当前缩进
+            out.push('}');
+        }
+        _ => write_value(out, value, config.ascii_only),  // 标量以及空数组、空对象与紧凑格式相同
+    }
+}
+
+/// 将 JSON 值写成一行，逗号和冒号之后各加一个空格，用于压缩短数组
+fn write_single_line(out: &mut String, value: &Value, ascii_only: bool) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_single_line(out, item, ascii_only);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_string_with(out, key, ascii_only);
+                out.push_str(": ");
+                write_single_line(out, item, ascii_only);
+            }
+            out.push('}');
+        }
+        _ => write_value(out, value, ascii_only),
+    }
+}
+
+/// 换行并输出 level 层缩进
+fn write_newline(out: &mut String, config: &PrettyConfig, level: usize) {
+    out.push('\n');
+    for _ in 0..level {
+        out.push_str(&config.indent);
+    }
+}
+
+/// 将 JSON 值输出为规范形式的紧凑 JSON 文本
+///
+/// 适合对 JSON 计算哈希或签名：语义相同的两个文档总是得到完全相同的输出。
+///
+/// 规则（与 RFC 8785 JSON Canonicalization Scheme 的做法一致）：
+/// - 不输出任何多余的空白
+/// - 每一层对象的键都按 Unicode 码点排序，与输入中的顺序无关
+/// - 字符串的转义规则与 to_string 相同
+/// - 数字统一格式，见 write_canonical_number
+///
+/// 举例：
+/// - 输入 {"b": 1, "a": [true, {"d": 0, "c": null}]} -> 输出 "{\"a\":[true,{\"c\":null,\"d\":0}],\"b\":1}"
+/// - 输入 1.0、1e0、1 -> 都输出 "1"
+pub fn to_string_canonical(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(&mut out, value);
+    out
+}
+
+/// 将 JSON 值以规范形式追加到 out 末尾
+fn write_canonical(out: &mut String, value: &Value) {
+    match value {
+        Value::Number(n) => write_canonical_number(out, n),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 元素之间用逗号分隔
+                }
+                write_canonical(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));  // str 按字节比较，UTF-8 的字节顺序就是码点顺序
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');  // 成员之间用逗号分隔
+                }
+                write_string(out, key);
+                out.push(':');
+                write_canonical(out, item);
+            }
+            out.push('}');
+        }
+        _ => write_value(out, value, false),  // null、布尔值和字符串本身就只有一种写法
+    }
+}
+
+/// 将数字以规范形式追加到 out 末尾
+///
+/// 详细解释：
+/// 1. 能用 i64 或 u64 表示的整数直接输出十进制数字
+/// 2. 其余数字按 f64 输出最短的、能精确还原的形式：
+///    - 绝对值在 1e-6 到 1e21 之间（或者为 0）时不用指数，整数值不带小数点，如 "100"、"0.5"
+///    - 否则用指数形式，指数总是带符号，如 "1e+21"、"1.5e-7"
+///    - -0 输出为 "0"
+///
+/// 所以 1、1.0、1e0、10e-1 都输出为 "1"。
+/// 打开 arbitrary_precision 时，超出 f64 精度的数字同样按 f64 输出，会丢失多余的精度
+fn write_canonical_number(out: &mut String, n: &serde_json::Number) {
+    if let Some(i) = n.as_i64() {
+        out.push_str(&i.to_string());
+    } else if let Some(u) = n.as_u64() {
+        out.push_str(&u.to_string());
+    } else {
+        let f = n.as_f64().unwrap_or(0.0);  // 解析出的数字都是有限的
+        let abs = f.abs();
+        if f == 0.0 {
+            out.push('0');  // 包括 -0
+        } else if (1e-6..1e21).contains(&abs) {
+            out.push_str(&f.to_string());  // Display 输出最短的十进制形式，整数值不带小数点
+        } else {
+            let text = format!("{:e}", f);  // 例如 "1e21"、"1.5e-7"
+            match text.split_once('e') {
+                Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+                    out.push_str(mantissa);
+                    out.push_str("e+");  // 正的指数也带上符号
+                    out.push_str(exponent);
+                }
+                _ => out.push_str(&text),
+            }
+        }
+    }
+}
+
+/// 将字符串加上引号并转义后追加到 out 末尾
+///
+/// 转义规则：
+/// - 引号和反斜杠：\" 和 \\
+/// - 常见控制字符：\n、\r、\t、\b、\f
+/// - 其余 U+0000 到 U+001F 的控制字符：\u00XX
+/// - 其他字符原样输出，包括正斜杠 /
+///
+/// JSON 允许把 / 写成 \/，解析时两种写法得到相同的字符串，
+/// 这里固定输出不转义的 /，与 serde_json 一致。
+/// 需要嵌入 HTML 的 <script> 中时，由调用方自己处理 "</" 的问题。
+pub(crate) fn write_string(out: &mut String, s: &str) {
+    write_string_with(out, s, false);
+}
+
+/// 与 write_string 相同，ascii_only 为 true 时非 ASCII 字符也写成 \uXXXX 转义
+///
+/// U+10000 及以上的字符先拆成 UTF-16 代理对，再把两个码元分别转义，
+/// 例如 😀（U+1F600）写成 \ud83d\ude00
+fn write_string_with(out: &mut String, s: &str, ascii_only: bool) {
+    out.push('"');  // 开始引号
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),        // 引号
+            '\\' => out.push_str("\\\\"),       // 反斜杠
+            '\n' => out.push_str("\\n"),        // 换行
+            '\r' => out.push_str("\\r"),        // 回车
+            '\t' => out.push_str("\\t"),        // 制表符
+            '\u{0008}' => out.push_str("\\b"),  // 退格
+            '\u{000C}' => out.push_str("\\f"),  // 换页
+            c if c < '\u{0020}' => out.push_str(&format!("\\u{:04x}", c as u32)),  // 其他控制字符
+            c if ascii_only && !c.is_ascii() => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    out.push_str(&format!("\\u{:04x}", unit));  // 每个 UTF-16 码元单独转义
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');  // 结束引号
+}
@@ -0,0 +1,309 @@
+//! 带位置信息的解析
+//!
+//! 普通的 parse 只返回 serde_json::Value，丢掉了每个值在输入中的位置。
+//! 这里提供一条平行的解析路径，返回 Spanned<SpannedValue> 树，
+//! 每个节点都带有产生它的那段文本的字节范围，对象成员的键也带有自己的范围。
+//! 编辑器、检查工具可以据此在原文中标出具体的值。
+//!
+//! 语法与 parser 模块完全相同，标量直接复用 parser 模块里的解析器。
+//!
+//! 打开 comments 特性后，允许注释时还会把每个节点前面的注释保存在 Spanned::comments 里，
+//! 见 parse_spanned_with_comments。
+
+use nom::{
+    Parser,
+    branch::alt,
+    character::complete::char as char_parser,
+    combinator::{all_consuming, cut, map},
+    sequence::preceded,
+};
+use serde_json::{Map, Number, Value};
+
+use crate::config::ParserConfig;
+use crate::lexer::{Span, span_of};
+#[cfg(feature = "comments")]
+use crate::parser::skip_whitespace;
+use crate::parser::{
+    PResult, check_depth, check_unterminated, parse_bool, parse_key, parse_literal_no_case, parse_non_finite, parse_null, parse_number, parse_string_with,
+    check_input_size, check_root, opt_trailing_comma, reject_empty, ws,
+};
+
+/// 带有位置信息的节点
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    /// 节点本身
+    pub value: T,
+    /// 产生这个节点的文本在输入中的范围
+    pub span: Span,
+    /// 紧挨在这个节点前面的注释，按出现顺序排列，保留 "//" 或 "/* */" 标记，行注释不含换行符
+    ///
+    /// 数组元素的注释在元素自己的节点上，对象成员的注释在键的节点上。
+    /// 只有允许注释的解析（parse_spanned_with_comments）才会填写，其余情况总是为空。
+    #[cfg(feature = "comments")]
+    pub comments: Vec<String>,
+}
+
+/// 带有位置信息的 JSON 值
+///
+/// 与 serde_json::Value 一一对应，但数组元素和对象成员都带有各自的位置。
+/// 对象成员按出现顺序保存在 Vec 里，重复的键也都会保留。
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedValue {
+    /// null
+    Null,
+    /// 布尔值
+    Bool(bool),
+    /// 数字
+    Number(Number),
+    /// 字符串
+    String(String),
+    /// 数组及其元素
+    Array(Vec<Spanned<SpannedValue>>),
+    /// 对象的成员，每个成员是带位置的键和带位置的值
+    Object(Vec<(Spanned<String>, Spanned<SpannedValue>)>),
+}
+
+impl SpannedValue {
+    /// 丢掉位置信息，转换为 serde_json::Value
+    /// 重复的键按后出现的值覆盖先出现的值处理，与 parse 的默认行为一致
+    pub fn into_value(self) -> Value {
+        match self {
+            SpannedValue::Null => Value::Null,
+            SpannedValue::Bool(b) => Value::Bool(b),
+            SpannedValue::Number(n) => Value::Number(n),
+            SpannedValue::String(s) => Value::String(s),
+            SpannedValue::Array(items) => Value::Array(items.into_iter().map(|item| item.value.into_value()).collect()),
+            SpannedValue::Object(members) => {
+                let mut map = Map::new();
+                for (key, item) in members {
+                    map.insert(key.value, item.value.into_value());
+                }
+                Value::Object(map)
+            }
+        }
+    }
+
+    /// 把 parser 模块解析出的标量转换为 SpannedValue
+    fn from_scalar(value: Value) -> Self {
+        match value {
+            Value::Bool(b) => SpannedValue::Bool(b),
+            Value::Number(n) => SpannedValue::Number(n),
+            Value::String(s) => SpannedValue::String(s),
+            _ => SpannedValue::Null,  // 标量解析器只会返回上面几种值和 null
+        }
+    }
+}
+
+/// 解析标量值：null、布尔值、NaN 和无穷大、数字或字符串
+fn spanned_scalar<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, SpannedValue> {
+    let (rest, value) = alt((
+        parse_null,
+        parse_bool,
+        |i| parse_literal_no_case(i, config),
+        |i| parse_non_finite(i, config),
+        |i| parse_number(i, config),
+        map(|i| parse_string_with(i, config), |s| Value::String(s.into_owned())),
+    )).parse(input)?;
+    Ok((rest, SpannedValue::from_scalar(value)))
+}
+
+/// 跳过节点之前的空白和注释，并收集其中的注释
+///
+/// 不允许注释时与 ws 相同，得到空列表。
+/// 块注释没有结束时交给 skip_whitespace 报告同样的 UnexpectedEof
+#[cfg(feature = "comments")]
+fn leading<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, Vec<String>> {
+    let mut comments = Vec::new();
+    if !config.allow_comments {
+        let (rest, _) = ws(config)(input)?;
+        return Ok((rest, comments));
+    }
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', '\r', '\n']);  // 与 ws 一样只跳过这四种空白
+        let len = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())  // 行注释到换行符为止
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            match body.find("*/") {
+                Some(i) => i + 4,  // 包括开始和结束标记
+                None => return skip_whitespace(rest, true).map(|(r, ())| (r, comments)),
+            }
+        } else {
+            return Ok((rest, comments));  // 既不是空白也不是注释
+        };
+        comments.push(rest[..len].trim_end_matches('\r').to_string());  // CRLF 换行的行注释
+        rest = &rest[len..];
+    }
+}
+
+/// 跳过节点之前的空白（以及注释），没有打开 comments 特性时注释直接丢弃
+#[cfg(not(feature = "comments"))]
+fn leading<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    ws(config)(input)
+}
+
+/// 构造节点，comments 是 leading 收集到的注释
+#[cfg(feature = "comments")]
+fn node<T>(value: T, span: Span, comments: Vec<String>) -> Spanned<T> {
+    Spanned { value, span, comments }
+}
+
+/// 构造节点，没有打开 comments 特性时没有注释
+#[cfg(not(feature = "comments"))]
+fn node<T>(value: T, span: Span, (): ()) -> Spanned<T> {
+    Spanned { value, span }
+}
+
+/// 解析对象的键，返回带位置的键名
+fn spanned_key<'a>(input: &'a str, source: &'a str, config: &ParserConfig) -> PResult<'a, Spanned<String>> {
+    let (input, comments) = leading(input, config)?;  // 键之前的空白（以及注释）
+    let (rest, key) = parse_key(input, config)?;
+    let span = span_of(source, input, rest);
+    let (rest, _) = ws(config)(rest)?;  // 键之后的空白
+    Ok((rest, node(key.into_owned(), span, comments)))
+}
+
+/// 开括号之后检查嵌套层数，不处理括号前后的空白
+fn spanned_open<'a>(input: &'a str, config: &ParserConfig, open: char, depth: usize) -> PResult<'a, ()> {
+    let (rest, _) = char_parser(open).parse(input)?;
+    check_depth(config, depth)(rest)  // 进入新的一层之前检查嵌套层数
+}
+
+/// 元素或成员之间的逗号，逗号之后的空白和注释留给下一个元素
+fn spanned_separator<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    let (rest, _) = preceded(ws(config), char_parser(',')).parse(input)?;
+    Ok((rest, ()))
+}
+
+/// 闭括号及其前面的空白，用 cut 锁定
+fn spanned_close<'a>(input: &'a str, config: &ParserConfig, close: char) -> PResult<'a, ()> {
+    let (rest, _) = cut(preceded(ws(config), char_parser(close))).parse(input)?;
+    Ok((rest, ()))
+}
+
+/// 解析数组，返回元素列表，不处理数组前后的空白
+fn spanned_array<'a>(input: &'a str, source: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, SpannedValue> {
+    let result = spanned_array_body(input, source, config, depth);
+    let (rest, items) = check_unterminated(result, input, config)?;
+    Ok((rest, SpannedValue::Array(items)))
+}
+
+/// 数组的方括号和元素列表，与 parser::parse_array_body 一样手写循环，让递归路径上的栈帧尽量小
+fn spanned_array_body<'a>(
+    input: &'a str,
+    source: &'a str,
+    config: &ParserConfig,
+    depth: usize,
+) -> PResult<'a, Vec<Spanned<SpannedValue>>> {
+    let (mut rest, ()) = spanned_open(input, config, '[', depth)?;  // 开始方括号
+    let mut items = Vec::new();
+    loop {
+        let start = if items.is_empty() {
+            rest
+        } else {
+            match spanned_separator(rest, config) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        match spanned_primary(start, source, config, depth + 1) {  // 元素位于下一层
+            Ok((after, item)) => {
+                items.push(item);
+                rest = after;
+            }
+            Err(nom::Err::Error(_)) => break,  // 不是值，回退到逗号之前
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, config, items.is_empty())?;
+    let (rest, ()) = spanned_close(rest, config, ']')?;  // 结束方括号
+    Ok((rest, items))
+}
+
+/// 解析对象，返回成员列表，不处理对象前后的空白
+fn spanned_object<'a>(input: &'a str, source: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, SpannedValue> {
+    let result = spanned_object_body(input, source, config, depth);
+    let (rest, members) = check_unterminated(result, input, config)?;
+    Ok((rest, SpannedValue::Object(members)))
+}
+
+/// 对象的成员：带位置的键和值
+type SpannedMember = (Spanned<String>, Spanned<SpannedValue>);
+
+/// 对象的大括号和成员列表，规则与 parser::parse_object_body 相同
+fn spanned_object_body<'a>(input: &'a str, source: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, Vec<SpannedMember>> {
+    let (mut rest, ()) = spanned_open(input, config, '{', depth)?;  // 开始大括号
+    let mut members = Vec::new();
+    loop {
+        let start = if members.is_empty() {
+            rest
+        } else {
+            match spanned_separator(rest, config) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        let (after, key) = match spanned_member_key(start, source, config) {
+            Ok(key) => key,
+            Err(nom::Err::Error(_)) => break,  // 不是成员，回退到逗号之前
+            Err(e) => return Err(e),
+        };
+        match spanned_primary(after, source, config, depth + 1) {  // 值，位于下一层
+            Ok((after, value)) => {
+                members.push((key, value));
+                rest = after;
+            }
+            Err(nom::Err::Error(e)) => return Err(nom::Err::Failure(e)),  // 冒号之后必须是值，与 cut 相同
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, config, members.is_empty())?;
+    let (rest, ()) = spanned_close(rest, config, '}')?;  // 结束大括号
+    Ok((rest, members))
+}
+
+/// 对象成员的键和之后的冒号
+fn spanned_member_key<'a>(input: &'a str, source: &'a str, config: &ParserConfig) -> PResult<'a, Spanned<String>> {
+    let (rest, key) = spanned_key(input, source, config)?;
+    let (rest, _) = char_parser(':').parse(rest)?;  // 冒号分隔符
+    Ok((rest, key))
+}
+
+/// 解析任意 JSON 值并记录它的位置，对应 parser::parse_primary
+///
+/// source 是完整的原始输入，用来把剩余输入换算成字节偏移量
+fn spanned_primary<'a>(
+    input: &'a str,
+    source: &'a str,
+    config: &ParserConfig,
+    depth: usize,
+) -> PResult<'a, Spanned<SpannedValue>> {
+    let (input, comments) = leading(input, config)?;  // 前导空白（以及注释）
+    let (rest, value) = match input.as_bytes().first() {
+        Some(b'[') => spanned_array(input, source, config, depth)?,  // 容器直接分派，不经过 alt
+        Some(b'{') => spanned_object(input, source, config, depth)?,
+        _ => spanned_any(input, source, config, depth)?,
+    };
+    let span = span_of(source, input, rest);  // 值本身的范围
+    let (rest, _) = ws(config)(rest)?;  // 尾随空白（以及注释）
+    Ok((rest, node(value, span, comments)))
+}
+
+/// 依次尝试每一种值的解析器
+fn spanned_any<'a>(input: &'a str, source: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, SpannedValue> {
+    alt((
+        |i| spanned_scalar(i, config),
+        |i| spanned_array(i, source, config, depth),
+        |i| spanned_object(i, source, config, depth),
+    )).parse(input)
+}
+
+/// 解析完整的 JSON 文档，空输入返回 EmptyInput，值后面不允许再有任何内容
+pub(crate) fn parse_document<'a>(input: &'a str, source: &'a str, config: &ParserConfig) -> PResult<'a, Spanned<SpannedValue>> {
+    check_input_size(input, config)?;
+    reject_empty(input, config)?;
+    check_root(input, config)?;
+    all_consuming(|i| spanned_primary(i, source, config, 0)).parse(input)
+}
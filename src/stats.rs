@@ -0,0 +1,59 @@
+//! 解析统计
+//!
+//! 在解析的同时统计文档里各类容器和标量的个数、最大嵌套层数等信息，
+//! 用于健康检查和监控面板，不需要在解析之后再遍历一遍 Value。
+
+use core::time::Duration;
+
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+#[cfg(feature = "serde_json")]
+use crate::{ParseError, ParserConfig, parser};
+
+/// 一次解析的统计信息
+///
+/// 根节点、数组元素和对象成员的值都各算一次，对象的键不计入 strings，
+/// 计数方式与 type_histogram 相同，只是布尔值和 null 不单独统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseStats {
+    /// 对象的个数
+    pub objects: usize,
+    /// 数组的个数
+    pub arrays: usize,
+    /// 字符串的个数
+    pub strings: usize,
+    /// 数字的个数
+    pub numbers: usize,
+    /// 解析消耗的字节数，成功时就是整个输入的长度（包括空白和字节顺序标记）
+    pub bytes: usize,
+    /// 最大的嵌套层数，与 max_depth 函数的计算方式相同：标量为 0，[] 为 1
+    pub max_depth: usize,
+    /// 解析花费的时间
+    pub elapsed: Duration,
+}
+
+/// 解析 JSON 文本，同时返回统计信息
+///
+/// 与 parse 完全相同，只是在同一遍解析中顺带统计各类值的个数和最大嵌套层数，
+/// 并记录解析花费的时间，不需要对结果再做一次遍历。
+/// 解析失败时只返回 ParseError，不返回统计信息。
+///
+/// 举例：
+/// - 输入 "[1, \"a\", {\"b\": null}]" -> arrays 1、objects 1、numbers 1、strings 1，max_depth 为 2
+/// - 输入 "42" -> numbers 1，max_depth 为 0，bytes 为 2
+/// - tests/sample.json 中的示例文档 -> objects 11、arrays 6、strings 16、numbers 11
+#[cfg(feature = "serde_json")]
+pub fn parse_with_stats(input: &str) -> Result<(Value, ParseStats), ParseError> {
+    let start = std::time::Instant::now();
+    let config = ParserConfig::default();
+    let ctx = parser::Context::with_stats(&config);
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 parse 一样跳过字节顺序标记
+    let (_, value) = parser::parse_document_in(text, &ctx).map_err(|e| ParseError::from_nom(input, e))?;
+    let stats = ParseStats {
+        bytes: input.len(),  // 整个输入都被消耗了
+        elapsed: start.elapsed(),
+        ..ctx.stats().unwrap_or_default()
+    };
+    Ok((value, stats))
+}
@@ -0,0 +1,362 @@
+//! 多值输入
+//!
+//! 处理一段输入中包含多个 JSON 值的情况，例如每行一个值的 NDJSON 日志，
+//! 首尾相接、没有分隔符的多个值，或者由大量记录组成的顶层数组。
+//! 输入分成多块陆续到达时（例如从 TCP 连接读取），可以用 StreamParser 边接收边解析。
+
+use nom::{
+    Parser,
+    branch::alt,
+    character::complete::char as char_parser,
+    combinator::all_consuming,
+    multi::many0,
+    sequence::{delimited, preceded},
+};
+use serde_json::Value;
+
+use crate::config::ParserConfig;
+use crate::error::{ErrorKind, InputError};
+use crate::parser::{Context, parse_primary, ws};
+use crate::{ParseError, parse};
+
+/// 逐行解析 NDJSON（JSON Lines）输入
+///
+/// 每个非空行都应该是一个完整的 JSON 值，返回的迭代器每次产出一行的解析结果。
+/// 某一行出错只影响这一行本身，后面的行照常解析。
+/// 空行（包括只含空白的行）会被跳过。
+///
+/// 错误中的行号和偏移量都按整个输入计算，而不是相对于这一行。
+///
+/// 举例：
+/// - 输入 "{\"a\":1}\n\n[2]\n" -> 依次产出 Ok({"a":1})、Ok([2])
+/// - 输入 "1\nx\n3" -> 依次产出 Ok(1)、Err(第 2 行)、Ok(3)
+pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())  // 跳过空行
+        .map(|(index, line)| {
+            parse(line).map_err(|mut e| {
+                e.line += index;  // 换算为整个输入中的行号
+                e.offset += line.as_ptr() as usize - input.as_ptr() as usize;  // 加上这一行在输入中的起始偏移量
+                e
+            })
+        })
+}
+
+/// 解析首尾相接的多个 JSON 值
+///
+/// 有些程序会连续输出多个 JSON 值，中间没有分隔符，或者只用空白分隔，
+/// 例如 {"a":1}{"b":2} 或 1 2 3。这个函数反复调用 parse_primary，
+/// 直到输入被完全消耗，返回按顺序排列的所有值。
+///
+/// 最后一个值不完整时返回错误，而不是丢弃它。
+///
+/// 举例：
+/// - 输入 "{\"a\":1}{\"b\":2}" -> 成功，返回两个对象
+/// - 输入 " 1 2\n3 " -> 成功，返回 [1, 2, 3]
+/// - 输入 "" -> 成功，返回空列表
+/// - 输入 "1 [2" -> 失败，最后一个数组没有结束
+pub fn parse_many(input: &str) -> Result<Vec<Value>, ParseError> {
+    let config = ParserConfig::default();
+    let ctx = Context::new(&config);
+    all_consuming(preceded(
+        ws(&config),  // 第一个值之前的空白（空输入时 parse_primary 不会消耗它）
+        many0(|i| parse_primary(i, &ctx, 0)),  // 每个值都会吃掉自己后面的空白
+    ))
+    .parse(input)
+    .map(|(_, values)| values)  // 只保留解析出的值
+    .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 逐个产出顶层数组的元素
+///
+/// 整个文件是一个巨大的记录数组时，不必先构造出完整的 Vec，
+/// 返回的迭代器每次只解析下一个元素，内存只和单个元素的大小有关。
+/// 外层的方括号、元素之间的逗号以及数组之后的内容都照常检查，
+/// 语法规则与 parse 相同（默认配置），元素的嵌套层数从 1 开始计算。
+///
+/// 遇到错误时产出这个错误，之后迭代器结束。
+/// 因为元素是边解析边产出的，出错之前的元素已经交给了调用方，
+/// 调用方需要检查每一项，不能只看前面的结果。
+///
+/// 举例：
+/// - 输入 "[{\"id\":1}, {\"id\":2}]" -> 依次产出 Ok({"id":1})、Ok({"id":2})
+/// - 输入 " [ ] " -> 什么也不产出
+/// - 输入 "[1, 2 3]" -> 依次产出 Ok(1)、Ok(2)、Err(缺少逗号)
+/// - 输入 "{\"a\": 1}" -> 产出 Err，顶层不是数组
+/// - 输入 "[1] x" -> 依次产出 Ok(1)、Err(TrailingData)
+pub fn stream_array(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    ArrayElements {
+        input,
+        rest: input.strip_prefix('\u{FEFF}').unwrap_or(input),  // 与 parse 一样跳过字节顺序标记
+        state: State::Start,
+        config: ParserConfig::default(),
+    }
+}
+
+/// stream_array 返回的迭代器
+struct ArrayElements<'a> {
+    /// 原始输入，用于计算错误位置
+    input: &'a str,
+    /// 还没有解析的输入
+    rest: &'a str,
+    /// 下一步要解析什么
+    state: State,
+    /// 解析配置
+    config: ParserConfig,
+}
+
+/// ArrayElements 的解析进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// 还没有遇到开始方括号
+    Start,
+    /// 下一个是元素
+    Element,
+    /// 刚解析完一个元素，下一个是逗号或结束方括号
+    AfterElement,
+    /// 已经遇到结束方括号，还要检查之后没有多余的内容
+    End,
+    /// 已经结束或者出错
+    Done,
+}
+
+impl<'a> ArrayElements<'a> {
+    /// 向前解析，直到得到下一个元素或者数组结束
+    fn step(&mut self) -> Result<Option<Value>, nom::Err<InputError<'a>>> {
+        loop {
+            match self.state {
+                State::Start => {
+                    let (rest, _) = delimited(ws(&self.config), char_parser('['), ws(&self.config)).parse(self.rest)?;  // 开始方括号
+                    self.rest = rest.strip_prefix(']').unwrap_or(rest);
+                    self.state = if rest.starts_with(']') { State::End } else { State::Element };  // 空数组
+                }
+                State::Element => {
+                    let ctx = Context::new(&self.config);  // 每个元素单独计数
+                    let (rest, value) = parse_primary(self.rest, &ctx, 1)?;  // 元素位于第一层，会吃掉前后的空白
+                    self.rest = rest;
+                    self.state = State::AfterElement;
+                    return Ok(Some(value));
+                }
+                State::AfterElement => {
+                    let (rest, c) = alt((char_parser(','), char_parser(']'))).parse(self.rest)?;
+                    self.rest = rest;
+                    self.state = if c == ',' { State::Element } else { State::End };
+                }
+                State::End => {
+                    all_consuming(ws(&self.config)).parse(self.rest)?;  // 数组之后只能有空白
+                    self.state = State::Done;
+                    return Ok(None);
+                }
+                State::Done => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Iterator for ArrayElements<'_> {
+    type Item = Result<Value, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.step() {
+            Ok(value) => value.map(Ok),
+            Err(e) => {
+                self.state = State::Done;  // 出错之后不再继续
+                Some(Err(ParseError::from_nom(self.input, e)))
+            }
+        }
+    }
+}
+
+/// 增量解析分块到达的输入
+///
+/// 每收到一块输入就调用一次 feed，StreamParser 把它追加到内部的缓冲区，
+/// 缓冲区里已经有一个完整的顶层值时返回 Ok(Some(值))，还需要更多输入时返回 Ok(None)。
+/// 解析出的值从缓冲区移除，之后的内容留给下一个值，所以同一个连接上可以连续发送多个值，
+/// 值之间可以用空白分隔，也可以首尾相接（与 parse_many 相同）。
+///
+/// 详细解释：
+/// 1. “不完整”和“不合法”按 ParseError::incomplete 区分：
+///    - 不完整：缓冲区在值的中间结束，例如 "[1, 2"、"{\"a\": \"x"、"tr"、"1e"，返回 Ok(None)
+///    - 不合法：无论后面再来什么都不可能成为合法的 JSON，例如 "[1 2"、"{1"，返回 Err
+/// 2. 顶层的数字之后没有任何字符时还不能确定它已经结束（"12" 后面可能还有 "3"），
+///    要等到后面出现空白或下一个值，或者调用 finish 表示输入已经结束
+/// 3. 出错之后缓冲区被清空，错误的位置按出错时缓冲区中的内容计算
+///
+/// 尚未完成的值不会在每次 feed 时都从头重新解析：新到的输入先用一个只认括号和引号的扫描器过一遍，
+/// 只有顶层的值可能已经结束（括号配对完毕、顶层字符串结束、顶层出现空白等），
+/// 或者缓冲区比上次尝试解析时长了一倍，才重新解析。
+/// 这样即使一个值被分成很多块，总的解析量也与输入的长度成正比；
+/// 代价是不合法的输入可能要再多收到一些内容才报错，最晚在缓冲区长度翻倍或者值可能结束时发现。
+/// 打开 allow_comments 或 json5 时，注释和单引号字符串里的括号会干扰扫描，每次 feed 都重新解析。
+///
+/// 举例：
+/// - feed("{\"a\": [1,") -> Ok(None)，feed(" 2]}") -> Ok(Some({"a": [1, 2]}))
+/// - feed("1 2 ") -> Ok(Some(1))，之后 next_value() -> Ok(Some(2))
+/// - feed("12") -> Ok(None)，finish() -> Ok(Some(12))
+/// - feed("[1 2") -> Err，缺少逗号
+#[derive(Debug, Clone, Default)]
+pub struct StreamParser {
+    /// 已经收到但还没有解析成值的输入
+    buffer: String,
+    /// 解析配置
+    config: ParserConfig,
+    /// 对缓冲区中尚未解析的值的扫描进度
+    scan: Scan,
+}
+
+/// 扫描缓冲区时的状态
+///
+/// 只跟踪括号的层数和是否位于字符串中，用来判断顶层的值是否可能已经结束，
+/// 不检查语法，语法错误留给真正的解析
+#[derive(Debug, Clone, Default)]
+struct Scan {
+    /// 已经扫描到的位置
+    offset: usize,
+    /// 当前位于几层括号之内
+    depth: usize,
+    /// 是否位于字符串中
+    in_string: bool,
+    /// 上一个字符是否是字符串中的反斜杠
+    escaped: bool,
+    /// 上次尝试解析时缓冲区的长度
+    parsed_len: usize,
+}
+
+impl StreamParser {
+    /// 按默认配置创建
+    pub fn new() -> Self {
+        StreamParser::default()
+    }
+
+    /// 按指定配置创建
+    pub fn with_config(config: ParserConfig) -> Self {
+        StreamParser { buffer: String::new(), config, scan: Scan::default() }
+    }
+
+    /// 追加一块输入，缓冲区里有完整的值时取出第一个
+    ///
+    /// 缓冲区里有多个完整的值时，其余的值留在缓冲区里，可以用 next_value 依次取出
+    ///
+    /// 设置了 max_input_bytes 时，追加之后缓冲区超过这个长度就不再解析，
+    /// 直接返回 ResourceLimit 并清空缓冲区，位置是缓冲区的开头
+    pub fn feed(&mut self, chunk: &str) -> Result<Option<Value>, ParseError> {
+        if self.config.max_input_bytes.is_some_and(|max| self.buffer.len() + chunk.len() > max) {
+            let err = ParseError::new(&self.buffer, &self.buffer, ErrorKind::ResourceLimit);
+            self.clear();  // 超出预算，丢掉尚未完成的值
+            return Err(err);
+        }
+        self.buffer.push_str(chunk);
+        if self.may_have_ended() {
+            self.next_value()
+        } else {
+            Ok(None)  // 值肯定还没有结束，先不解析
+        }
+    }
+
+    /// 扫描新到的输入，判断是否值得重新解析
+    ///
+    /// 顶层的值可能已经结束，或者缓冲区比上次尝试解析时长了一倍时返回 true
+    fn may_have_ended(&mut self) -> bool {
+        if self.config.allow_comments || self.config.json5 {
+            return true;  // 注释和单引号字符串里的括号会干扰扫描
+        }
+        let scan = &mut self.scan;
+        let mut ended = false;
+        for c in self.buffer[scan.offset..].chars() {
+            if scan.in_string {
+                if scan.escaped {
+                    scan.escaped = false;
+                } else if c == '\\' {
+                    scan.escaped = true;
+                } else if c == '"' {
+                    scan.in_string = false;
+                    ended |= scan.depth == 0;  // 顶层的字符串结束
+                }
+                continue;
+            }
+            match c {
+                '"' => scan.in_string = true,
+                '[' | '{' => scan.depth += 1,
+                ']' | '}' => {
+                    scan.depth = scan.depth.saturating_sub(1);
+                    ended |= scan.depth == 0;  // 括号配对完毕，或者多出来的右括号
+                }
+                '0'..='9' | '-' | '+' | '.' => {}  // 顶层的数字要等到后面出现别的字符
+                _ => ended |= scan.depth == 0,  // 顶层的空白、字面量或者其他字符
+            }
+        }
+        scan.offset = self.buffer.len();
+        ended || self.buffer.len() >= 2 * scan.parsed_len
+    }
+
+    /// 不追加输入，从缓冲区取出下一个完整的值
+    pub fn next_value(&mut self) -> Result<Option<Value>, ParseError> {
+        let text = self.buffer.as_str();
+        let ctx = Context::new(&self.config);
+        match parse_primary::<Value>(text, &ctx, 0) {
+            Ok((rest, value)) => {
+                if value.is_number() && rest.is_empty() && !text.ends_with([' ', '\t', '\r', '\n']) {
+                    self.scan.parsed_len = text.len();
+                    return Ok(None);  // 数字后面可能还有数字
+                }
+                let consumed = text.len() - rest.len();
+                self.buffer.drain(..consumed);
+                self.scan = Scan::default();  // 剩下的内容属于下一个值，重新扫描
+                Ok(Some(value))
+            }
+            Err(e) => {
+                let err = ParseError::from_nom(text, e);
+                if err.incomplete {
+                    if ws(&self.config)(text).is_ok_and(|(rest, _)| rest.is_empty()) {
+                        self.clear();  // 只剩下空白，不必保留
+                    } else {
+                        self.scan.parsed_len = text.len();
+                    }
+                    return Ok(None);
+                }
+                self.clear();  // 出错之后的内容已经没有意义
+                Err(err)
+            }
+        }
+    }
+
+    /// 输入已经结束，取出缓冲区里的最后一个值
+    ///
+    /// 缓冲区只有空白时返回 Ok(None)；
+    /// 最后一个值不完整时返回错误，例如 UnterminatedArray，此时缓冲区同样被清空
+    pub fn finish(&mut self) -> Result<Option<Value>, ParseError> {
+        let text = self.buffer.as_str();
+        if ws(&self.config)(text).is_ok_and(|(rest, _)| rest.is_empty()) {
+            self.clear();
+            return Ok(None);
+        }
+        let ctx = Context::new(&self.config);
+        let result = parse_primary::<Value>(text, &ctx, 0)
+            .map(|(rest, value)| (text.len() - rest.len(), value))
+            .map_err(|e| ParseError::from_nom(text, e));
+        match result {
+            Ok((consumed, value)) => {
+                self.buffer.drain(..consumed);  // 之后如果还有值，可以继续调用 finish
+                self.scan = Scan::default();
+                Ok(Some(value))
+            }
+            Err(err) => {
+                self.clear();
+                Err(err)
+            }
+        }
+    }
+
+    /// 清空缓冲区和扫描状态
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.scan = Scan::default();
+    }
+
+    /// 已经收到但还没有解析成值的输入
+    pub fn buffered(&self) -> &str {
+        &self.buffer
+    }
+}
@@ -0,0 +1,196 @@
+//! 只做校验的解析器
+//!
+//! 语法与 parser 模块完全相同，但不构造 Value：
+//! 字符串只检查字符和转义是否合法，不拼接内容；
+//! 数组和对象只检查结构，不收集元素和成员。
+//! 因此校验过程中不会为字符串、数组和对象分配内存，
+//! 适合只需要判断“是不是合法的 JSON”的场景。
+//!
+//! 空白、数字、null 和布尔值直接复用 parser 模块里的解析器，输出类型为 ()。
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::char as char_parser,
+    combinator::{all_consuming, value},
+    multi::many0,
+    sequence::delimited,
+};
+
+use crate::config::{InvalidEscapePolicy, ParserConfig};
+use crate::parser::{
+    PResult, check_input_size, check_root, check_unterminated, close_container, closing_quote, is_plain_char, list_separator, open_container, opt_trailing_comma,
+    parse_bool, parse_escaped_char, parse_literal_no_case, parse_non_finite, parse_null, parse_number, recognize_identifier, reject_empty, unterminated_string, ws,
+};
+
+/// 校验由 quote 包围的字符串
+///
+/// 连续的普通字符用 take_while1 一次跳过，遇到反斜杠才逐个检查转义，
+/// 规则与 parser 模块里的字符串解析完全相同，没有结束引号时同样返回 UnterminatedString
+fn skip_quoted_string(input: &str, quote: char, policy: InvalidEscapePolicy) -> PResult<'_, ()> {
+    let (rest, _) = char_parser(quote)(input)?;  // 开始引号
+    let (rest, _) = many0(alt((
+        value((), take_while1(|c| is_plain_char(c, quote))),  // 一段普通字符
+        value((), |i| parse_escaped_char(i, quote, policy)),  // 一个转义序列
+    )))
+    .parse(rest)
+    .map_err(|e| unterminated_string(input, e))?;
+    let (rest, _) = closing_quote(input, rest, quote)?;  // 结束引号
+    Ok((rest, ()))
+}
+
+/// 按配置校验字符串，json5 打开时也接受单引号字符串
+fn skip_string<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    let policy = config.invalid_escape;
+    if config.json5 {
+        alt((|i| skip_quoted_string(i, '"', policy), |i| skip_quoted_string(i, '\'', policy))).parse(input)
+    } else {
+        skip_quoted_string(input, '"', policy)
+    }
+}
+
+/// 按配置校验对象的键，json5 打开时也接受不带引号的标识符
+fn skip_key<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    if config.json5 {
+        alt((|i| skip_string(i, config), value((), recognize_identifier))).parse(input)
+    } else {
+        skip_string(input, config)
+    }
+}
+
+/// 校验数组
+fn skip_array<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    let result = skip_array_body(input, config, depth);
+    check_unterminated(result, input, config)
+}
+
+/// 数组的方括号和元素列表，与 parser::parse_array_body 一样手写循环，让递归路径上的栈帧尽量小
+fn skip_array_body<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    let (mut rest, ()) = open_container(input, config, '[', depth)?;
+    let mut empty = true;
+    loop {
+        let start = if empty {
+            rest
+        } else {
+            match list_separator(rest, config) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        match skip_primary(start, config, depth + 1) {  // 元素位于下一层
+            Ok((after, ())) => {
+                rest = after;
+                empty = false;
+            }
+            Err(nom::Err::Error(_)) => break,  // 不是值，回退到逗号之前
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, config, empty)?;
+    close_container(rest, config, ']')  // 结束方括号
+}
+
+/// 对象成员的键、前后的空白和冒号
+fn skip_member_key<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    let (rest, _) = delimited(ws(config), |i| skip_key(i, config), ws(config)).parse(input)?;
+    let (rest, _) = char_parser(':').parse(rest)?;  // 冒号分隔符
+    Ok((rest, ()))
+}
+
+/// 校验对象
+/// 不记录键，因此不检查重复键
+fn skip_object<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    let result = skip_object_body(input, config, depth);
+    check_unterminated(result, input, config)
+}
+
+/// 对象的大括号和成员列表，规则与 parser::parse_object_body 相同
+fn skip_object_body<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    let (mut rest, ()) = open_container(input, config, '{', depth)?;
+    let mut empty = true;
+    loop {
+        let start = if empty {
+            rest
+        } else {
+            match list_separator(rest, config) {
+                Ok((after, ())) => after,
+                Err(nom::Err::Error(_)) => break,  // 没有逗号，列表结束
+                Err(e) => return Err(e),
+            }
+        };
+        let after = match skip_member_key(start, config) {
+            Ok((after, ())) => after,
+            Err(nom::Err::Error(_)) => break,  // 不是成员，回退到逗号之前
+            Err(e) => return Err(e),
+        };
+        match skip_primary(after, config, depth + 1) {  // 值，位于下一层
+            Ok((after, ())) => {
+                rest = after;
+                empty = false;
+            }
+            Err(nom::Err::Error(e)) => return Err(nom::Err::Failure(e)),  // 冒号之后必须是值，与 cut 相同
+            Err(e) => return Err(e),
+        }
+    }
+    let (rest, ()) = opt_trailing_comma(rest, config, empty)?;
+    close_container(rest, config, '}')  // 结束大括号
+}
+
+/// 校验任意 JSON 值，对应 parser::parse_primary
+///
+/// 与 parse_primary 一样，数组和对象按第一个字符直接分派，不经过 alt，
+/// 让递归路径上的栈帧尽量小
+fn skip_primary<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    let (input, _) = ws(config)(input)?;  // 前导空白（以及注释）
+    let (rest, ()) = match input.as_bytes().first() {
+        Some(b'[') => skip_array(input, config, depth)?,
+        Some(b'{') => skip_object(input, config, depth)?,
+        _ => skip_any(input, config, depth)?,
+    };
+    ws(config)(rest)  // 尾随空白（以及注释）
+}
+
+/// 依次尝试每一种值的校验器
+fn skip_any<'a>(input: &'a str, config: &ParserConfig, depth: usize) -> PResult<'a, ()> {
+    alt((
+        parse_null,
+        parse_bool,
+        |i| parse_literal_no_case(i, config),
+        |i| parse_non_finite(i, config),
+        |i| parse_number(i, config),  // 数字只检查能否表示，不保存
+        |i| skip_string(i, config),
+        |i| skip_array(i, config, depth),
+        |i| skip_object(i, config, depth),
+    )).parse(input)
+}
+
+/// 跳过输入开头的一个 JSON 值，不构造它
+///
+/// 这是一个普通的 nom 解析器，可以和其他组合子组合使用，
+/// 例如在自己的解析器里只解析关心的字段，其余字段的值用 skip_value 丢掉。
+/// 值前后的空白会被一起吃掉，值后面的内容原样留在剩余输入里。
+///
+/// 语法按默认配置检查：字符串里的括号和转义不会被误认为结构，
+/// 嵌套层数超过默认的 max_depth 时失败。
+/// 失败时返回 nom 的 Error 或 Failure，错误里的输入指向出错的位置；
+/// 需要具体的错误类别时，可以对同一段输入调用 validate。
+///
+/// 举例：
+/// - 输入 "{\"a\": \"}]\"}, 2" -> 剩余 ", 2"
+/// - 输入 " [1, [2, [3]]] rest" -> 剩余 "rest"
+/// - 输入 "[1, 2" -> 失败
+pub fn skip_value(input: &str) -> IResult<&str, ()> {
+    skip_primary(input, &ParserConfig::default(), 0)
+        .map_err(|e| e.map(|e| nom::error::Error::new(e.input, nom::error::ErrorKind::Fail)))  // 换成 nom 自带的错误类型
+}
+
+/// 校验完整的 JSON 文档，值后面不允许再有任何内容
+/// 空输入和只有空白的输入与 parse 一样返回 EmptyInput
+pub(crate) fn validate_document<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, ()> {
+    check_input_size(input, config)?;
+    reject_empty(input, config)?;
+    check_root(input, config)?;
+    all_consuming(|i| skip_primary(i, config, 0)).parse(input)
+}
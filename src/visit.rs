@@ -0,0 +1,195 @@
+//! 事件驱动的解析
+//!
+//! 不构造任何树，而是在解析过程中按顺序把事件（开始对象、键、标量、结束数组等）
+//! 交给调用方实现的 Visitor。只需要从很大的文档里取出少数几个字段时，
+//! 可以在回调里挑出需要的内容，其余的值解析完就丢掉，不占用内存。
+//!
+//! 语法与 parser 模块完全相同，标量直接复用 parser 模块里的解析器。
+
+use alloc::borrow::Cow;
+
+use nom::{
+    Parser,
+    character::complete::char as char_parser,
+    combinator::{all_consuming, cut},
+};
+
+use crate::config::ParserConfig;
+use crate::error::ParseError;
+use crate::parser::{
+    PResult, check_depth, check_unterminated, parse_bool, parse_key, parse_literal_no_case, parse_non_finite, parse_null, parse_number, parse_string_with, reject_empty, ws,
+};
+
+/// 标量事件携带的值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scalar<'a> {
+    /// null，allow_nan 模式下的 NaN 和无穷大也报告为 null
+    Null,
+    /// 布尔值
+    Bool(bool),
+    /// 数字在输入中的原文，需要时由调用方自己转换
+    Number(&'a str),
+    /// 字符串，不含转义时直接借用输入
+    String(Cow<'a, str>),
+}
+
+/// 接收解析事件的回调
+///
+/// 所有方法都有什么都不做的默认实现，只需要覆盖关心的事件。
+/// 事件的顺序与文本中的顺序一致，例如 {"a": [1]} 依次产生：
+/// start_object、key("a")、start_array、scalar(Number("1"))、end_array、end_object
+///
+/// 事件在解析过程中立即发出，所以输入后面出错时，
+/// 出错之前的事件已经发给了 Visitor，调用方需要以最终的返回值为准
+pub trait Visitor<'a> {
+    /// 遇到 '{'
+    fn start_object(&mut self) {}
+
+    /// 遇到对象成员的键，随后的事件属于这个键对应的值
+    fn key(&mut self, key: Cow<'a, str>) {
+        let _ = key;
+    }
+
+    /// 遇到 '}'
+    fn end_object(&mut self) {}
+
+    /// 遇到 '['
+    fn start_array(&mut self) {}
+
+    /// 遇到 ']'
+    fn end_array(&mut self) {}
+
+    /// 遇到标量：null、布尔值、数字或字符串
+    fn scalar(&mut self, scalar: Scalar<'a>) {
+        let _ = scalar;
+    }
+}
+
+/// 解析 JSON 文本，把事件依次交给 visitor
+///
+/// 语法规则与 parse 完全相同，合法时返回 Ok(())，否则返回 ParseError。
+/// 嵌套层数同样受默认的 max_depth 限制。
+///
+/// 举例：统计文档里一共有多少个数字
+///
+/// ```
+/// use json_parser::{Scalar, Visitor, parse_with_visitor};
+///
+/// struct CountNumbers(usize);
+///
+/// impl<'a> Visitor<'a> for CountNumbers {
+///     fn scalar(&mut self, scalar: Scalar<'a>) {
+///         if let Scalar::Number(_) = scalar {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let mut counter = CountNumbers(0);
+/// parse_with_visitor("{\"a\": [1, 2], \"b\": 3}", &mut counter).unwrap();
+/// assert_eq!(counter.0, 3);
+/// ```
+pub fn parse_with_visitor<'a>(input: &'a str, visitor: &mut impl Visitor<'a>) -> Result<(), ParseError> {
+    let config = ParserConfig::default();
+    let text = input.strip_prefix('\u{FEFF}').unwrap_or(input);  // 与 parse 一样跳过字节顺序标记
+    reject_empty(text, &config).map_err(|e| ParseError::from_nom(input, e))?;  // 空输入和只有空白的输入返回 EmptyInput
+    all_consuming(|i| visit_value(i, &config, 0, visitor))
+        .parse(text)
+        .map(|(_, ())| ())
+        .map_err(|e| ParseError::from_nom(input, e))  // 将 nom 错误转换为 ParseError
+}
+
+/// 解析任意 JSON 值并发出对应的事件，对应 parser::parse_primary
+///
+/// 值前后的空白都会被吃掉；depth 是当前值外层已有多少层数组/对象
+fn visit_value<'a>(input: &'a str, config: &ParserConfig, depth: usize, visitor: &mut impl Visitor<'a>) -> PResult<'a, ()> {
+    let (input, _) = ws(config)(input)?;  // 前导空白（以及注释）
+    let (rest, ()) = if let Some(rest) = input.strip_prefix('[') {
+        check_unterminated(visit_array(rest, config, depth, visitor), input, config)?
+    } else if let Some(rest) = input.strip_prefix('{') {
+        check_unterminated(visit_object(rest, config, depth, visitor), input, config)?
+    } else {
+        let (rest, scalar) = parse_scalar(input, config)?;
+        visitor.scalar(scalar);
+        (rest, ())
+    };
+    let (rest, _) = ws(config)(rest)?;  // 尾随空白（以及注释）
+    Ok((rest, ()))
+}
+
+/// 解析标量，规则与 parse_primary 中对应的分支相同
+fn parse_scalar<'a>(input: &'a str, config: &ParserConfig) -> PResult<'a, Scalar<'a>> {
+    if let Ok((rest, ())) = parse_null::<()>(input) {
+        return Ok((rest, Scalar::Null));
+    }
+    if let Ok((rest, ())) = parse_bool::<()>(input) {
+        return Ok((rest, Scalar::Bool(input.starts_with('t'))));
+    }
+    if let Ok((rest, ())) = parse_literal_no_case::<()>(input, config) {
+        let scalar = match input.as_bytes()[0].to_ascii_lowercase() {
+            b'n' => Scalar::Null,
+            first => Scalar::Bool(first == b't'),  // true 或 false
+        };
+        return Ok((rest, scalar));
+    }
+    if let Ok((rest, ())) = parse_non_finite::<()>(input, config) {
+        return Ok((rest, Scalar::Null));  // 与 parse 一样，NaN 和无穷大按 null 处理
+    }
+    if input.starts_with(['"', '\'']) {
+        let (rest, s) = parse_string_with(input, config)?;
+        return Ok((rest, Scalar::String(s)));
+    }
+    let (rest, ()) = parse_number::<()>(input, config)?;
+    let len = input.len() - rest.len();
+    let text = input[..len].trim_end_matches([' ', '\t', '\r', '\n']);  // 数字解析器会吃掉后面的空白
+    Ok((&input[text.len()..], Scalar::Number(text)))
+}
+
+/// 解析 '[' 之后的数组内容，直到结束方括号
+fn visit_array<'a>(input: &'a str, config: &ParserConfig, depth: usize, visitor: &mut impl Visitor<'a>) -> PResult<'a, ()> {
+    let (input, _) = ws(config)(input)?;
+    check_depth(config, depth)(input)?;  // 进入新的一层之前检查嵌套层数
+    visitor.start_array();
+    let mut input = input;
+    if let Some(rest) = input.strip_prefix(']') {
+        visitor.end_array();  // 空数组
+        return Ok((rest, ()));
+    }
+    loop {
+        let (rest, ()) = cut(|i| visit_value(i, config, depth + 1, &mut *visitor)).parse(input)?;  // 元素位于下一层
+        if let Some(rest) = rest.strip_prefix(',') {
+            input = rest;  // 还有下一个元素
+        } else {
+            let (rest, _) = cut(char_parser(']')).parse(rest)?;  // 结束方括号
+            visitor.end_array();
+            return Ok((rest, ()));
+        }
+    }
+}
+
+/// 解析 '{' 之后的对象内容，直到结束大括号
+fn visit_object<'a>(input: &'a str, config: &ParserConfig, depth: usize, visitor: &mut impl Visitor<'a>) -> PResult<'a, ()> {
+    let (input, _) = ws(config)(input)?;
+    check_depth(config, depth)(input)?;  // 进入新的一层之前检查嵌套层数
+    visitor.start_object();
+    let mut input = input;
+    if let Some(rest) = input.strip_prefix('}') {
+        visitor.end_object();  // 空对象
+        return Ok((rest, ()));
+    }
+    loop {
+        let (rest, _) = ws(config)(input)?;
+        let (rest, key) = cut(|i| parse_key(i, config)).parse(rest)?;  // 键
+        visitor.key(key);
+        let (rest, _) = ws(config)(rest)?;
+        let (rest, _) = cut(char_parser(':')).parse(rest)?;  // 冒号分隔符
+        let (rest, ()) = cut(|i| visit_value(i, config, depth + 1, &mut *visitor)).parse(rest)?;  // 值位于下一层
+        if let Some(rest) = rest.strip_prefix(',') {
+            input = rest;  // 还有下一个成员
+        } else {
+            let (rest, _) = cut(char_parser('}')).parse(rest)?;  // 结束大括号
+            visitor.end_object();
+            return Ok((rest, ()));
+        }
+    }
+}
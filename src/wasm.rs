@@ -0,0 +1,44 @@
+//! 适合跨越 JS 边界的错误
+//!
+//! 编译到 wasm32-unknown-unknown 的浏览器端工具需要把解析错误交给 JavaScript，
+//! ParseError 里的 ErrorKind 是 Rust 枚举，不方便直接传过去。
+//! 这里的 ErrorInfo 只有行号、列号和错误信息三个字段，
+//! 实现了 Serialize，可以用 serde-wasm-bindgen 等工具原样转换为 JS 对象。
+//!
+//! 解析器本身只依赖 core 和 alloc（关闭 std 特性后是 no_std 的），
+//! 不使用线程、文件系统等在 wasm 上不可用的功能，可以在浏览器里直接运行。
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ParseError, parse};
+
+/// 可以序列化的解析错误
+///
+/// 序列化为 JSON 时的形状是 {"line": 1, "column": 5, "message": "..."}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorInfo {
+    /// 出错位置所在的行，从 1 开始
+    pub line: usize,
+    /// 出错位置所在的列，从 1 开始，按字符计数
+    pub column: usize,
+    /// 错误信息，即 ErrorKind 的 Display 输出，不含位置
+    pub message: String,
+}
+
+impl From<ParseError> for ErrorInfo {
+    fn from(err: ParseError) -> Self {
+        ErrorInfo { line: err.line, column: err.column, message: err.kind.to_string() }
+    }
+}
+
+/// 解析 JSON 文本，错误转换为可以序列化的 ErrorInfo
+///
+/// 语法规则与 parse 完全相同，只是错误的类型不同。
+///
+/// 举例：
+/// - 输入 "[1, 2]" -> 成功
+/// - 输入 "[1,\n 2" -> 失败，ErrorInfo { line: 2, column: 3, message: "输入意外结束" }
+pub fn parse_to_json_error(input: &str) -> Result<Value, ErrorInfo> {
+    parse(input).map_err(ErrorInfo::from)
+}
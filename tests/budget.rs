@@ -0,0 +1,41 @@
+//! 输入字节数的限制
+//!
+//! 设置 max_input_bytes 后，超长的输入在解析之前就被拒绝，StreamParser 在追加输入时检查。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, ParserConfig, StreamParser};
+use serde_json::json;
+
+#[test]
+fn oversized_input_is_rejected_before_parsing() {
+    let parser = Parser::new().max_input_bytes(1024);
+    let huge = format!("[{}", "[".repeat(1 << 20));  // 解析的话会先遇到 DepthExceeded
+    let err = parser.parse(&huge).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ResourceLimit);
+    assert_eq!(err.offset, 0);
+    let garbage = "x".repeat(2048);  // 根本不是 JSON，也不会走到语法检查
+    assert_eq!(parser.parse(&garbage).unwrap_err().kind, ErrorKind::ResourceLimit);
+    assert_eq!(parser.parse_native(&garbage).unwrap_err().kind, ErrorKind::ResourceLimit);
+}
+
+#[test]
+fn input_within_budget_is_parsed() {
+    let parser = Parser::new().max_input_bytes(7);
+    assert_eq!(parser.parse("[1,2,3]").unwrap(), json!([1, 2, 3]));  // 恰好 7 个字节
+    assert_eq!(parser.parse("[1,2,3] ").unwrap_err().kind, ErrorKind::ResourceLimit);  // 空白也算
+    assert_eq!(parser.parse("\u{FEFF}[1,2,3]").unwrap(), json!([1, 2, 3]));  // 字节顺序标记不计入
+}
+
+#[test]
+fn stream_parser_checks_each_chunk() {
+    let config = Parser::new().max_input_bytes(8).config().clone();
+    let mut stream = StreamParser::with_config(config);
+    assert_eq!(stream.feed("[1, ").unwrap(), None);
+    assert_eq!(stream.feed("2] ").unwrap(), Some(json!([1, 2])));  // 取出的值不再占用预算
+    assert_eq!(stream.feed("[3, 4, ").unwrap(), None);
+    let err = stream.feed("5]").unwrap_err();  // 缓冲区将超过 8 个字节
+    assert_eq!(err.kind, ErrorKind::ResourceLimit);
+    assert_eq!(stream.buffered(), "");
+    assert_eq!(StreamParser::with_config(ParserConfig::default()).feed(&"1 ".repeat(100)).unwrap(), Some(json!(1)));
+}
@@ -0,0 +1,43 @@
+//! 规范形式的输出
+//!
+//! 语义相同的文档无论键的顺序和写法如何，to_string_canonical 的输出都完全相同。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{parse, to_string, to_string_canonical};
+
+#[test]
+fn key_order_does_not_matter() {
+    let inputs = [
+        r#"{"b": 1, "a": {"y": [1, {"q": 2, "p": 3}], "x": null}, "c": "s"}"#,
+        r#"{"a": {"x": null, "y": [1, {"p": 3, "q": 2}]}, "c": "s", "b": 1}"#,
+        r#"{"c": "s", "b": 1, "a": {"y": [1, {"q": 2, "p": 3}], "x": null}}"#,
+    ];
+    let expected = r#"{"a":{"x":null,"y":[1,{"p":3,"q":2}]},"b":1,"c":"s"}"#;
+    for input in inputs {
+        let value = parse(input).unwrap();
+        assert_eq!(to_string_canonical(&value), expected, "{}", input);
+        assert_ne!(to_string(&value), expected);  // 普通输出保持输入中的顺序
+    }
+}
+
+#[test]
+fn keys_sorted_by_code_point() {
+    let value = parse(r#"{"é": 1, "z": 2, "A": 3, "😀": 4, "￿": 5, "": 6}"#).unwrap();
+    assert_eq!(to_string_canonical(&value), "{\"\":6,\"A\":3,\"z\":2,\"é\":1,\"\u{ffff}\":5,\"😀\":4}");
+}
+
+#[test]
+fn array_order_is_kept() {
+    let a = to_string_canonical(&parse("[2, 1]").unwrap());
+    assert_eq!(a, "[2,1]");  // 数组的顺序有意义，不排序
+    assert_ne!(a, to_string_canonical(&parse("[1, 2]").unwrap()));
+}
+
+#[test]
+fn number_spelling_does_not_matter() {
+    let a = to_string_canonical(&parse(r#"{"n": 1.0, "m": 1E-7}"#).unwrap());
+    let b = to_string_canonical(&parse(r#"{"m": 0.0000001, "n": 1}"#).unwrap());
+    assert_eq!(a, r#"{"m":1e-7,"n":1}"#);
+    assert_eq!(a, b);
+}
@@ -0,0 +1,60 @@
+//! 极深嵌套的值
+//!
+//! parse_iterative 可以解析任意深的文档，之后对结果的处理也不能依赖递归。
+//! 这里的文档有 100000 层，远超默认栈能承受的递归深度；
+//! json::Value 的析构用 1000000 层的值检查。
+
+#![cfg(feature = "serde_json")]
+
+use std::mem::ManuallyDrop;
+
+use json_parser::{deep_clone, json, parse, parse_iterative, values_equal};
+
+/// depth 层交替嵌套的数组和对象，最里面的值是 leaf
+fn nested(depth: usize, leaf: &str) -> String {
+    format!("{}{}{}", "[{\"k\":".repeat(depth / 2), leaf, "}]".repeat(depth / 2))
+}
+
+#[test]
+fn values_equal_deep() {
+    // serde_json::Value 的析构是递归的，这里不释放结果，避免测试本身栈溢出
+    let a = ManuallyDrop::new(parse_iterative(&nested(100_000, "1")).unwrap());
+    let b = ManuallyDrop::new(parse_iterative(&nested(100_000, "1")).unwrap());
+    let c = ManuallyDrop::new(parse_iterative(&nested(100_000, "2")).unwrap());
+    assert!(values_equal(&a, &b));
+    assert!(!values_equal(&a, &c));  // 只有最里面的值不同
+}
+
+#[test]
+fn deep_clone_deep() {
+    let a = ManuallyDrop::new(parse_iterative(&nested(100_000, "1")).unwrap());
+    let copy = ManuallyDrop::new(deep_clone(&a));
+    assert!(values_equal(&a, &copy));
+    let c = ManuallyDrop::new(parse_iterative(&nested(100_000, "2")).unwrap());
+    assert!(!values_equal(&copy, &c));
+}
+
+#[test]
+fn deep_clone_matches_clone() {
+    let v = parse(r#"{"z": [1, 2.5, {"b": null, "a": [true, []]}], "a": {}, "s": "x"}"#).unwrap();
+    let copy = deep_clone(&v);
+    assert_eq!(copy, v.clone());
+    assert_eq!(copy.as_object().unwrap().keys().collect::<Vec<_>>(), ["z", "a", "s"]);  // 成员顺序不变
+    assert_eq!(deep_clone(&parse("7").unwrap()), 7);
+}
+
+#[test]
+fn native_value_drop_deep() {
+    let mut value = json::Value::Null;
+    for i in 0..1_000_000 {
+        value = if i % 2 == 0 {
+            json::Value::Array(vec![value, json::Value::Bool(true)])
+        } else {
+            json::Value::Object(json::Map::from([("k".into(), value)]))
+        };
+    }
+    drop(value);  // 派生的析构在这里会栈溢出
+    let shallow = json::parse(r#"{"a": [1, {"b": []}], "c": "x"}"#).unwrap();
+    drop(shallow.clone());
+    assert_eq!(shallow.get("c").and_then(json::Value::as_str), Some("x"));
+}
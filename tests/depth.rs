@@ -0,0 +1,42 @@
+//! max_depth 函数
+//!
+//! 计算值的嵌套层数，计数方式与 ParserConfig::max_depth 相同。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, max_depth};
+use serde_json::json;
+
+#[test]
+fn scalars_and_empty_containers() {
+    for scalar in [json!(null), json!(true), json!(1), json!("[[]]")] {
+        assert_eq!(max_depth(&scalar), 0, "{}", scalar);
+    }
+    assert_eq!(max_depth(&json!([])), 1);
+    assert_eq!(max_depth(&json!({})), 1);
+    assert_eq!(max_depth(&json!([[]])), 2);  // 空容器本身算一层
+    assert_eq!(max_depth(&json!({"a": {}})), 2);
+}
+
+#[test]
+fn nested_arrays() {
+    assert_eq!(max_depth(&json!([1])), 1);
+    assert_eq!(max_depth(&json!([[1]])), 2);
+    assert_eq!(max_depth(&json!([[[[1]]]])), 4);
+}
+
+#[test]
+fn mixed_nesting() {
+    assert_eq!(max_depth(&json!({"a": [1, {"b": {}}], "c": 2})), 4);
+    assert_eq!(max_depth(&json!([1, [2, [3]], [4], {"x": [[]]}])), 4);  // 取最深的分支
+    assert_eq!(max_depth(&json!([{"a": 1}, [[[]]], "s"])), 4);
+}
+
+#[test]
+fn same_count_as_parser_limit() {
+    let input = r#"{"a": [1, {"b": {}}], "c": 2}"#;
+    let depth = max_depth(&Parser::new().parse(input).unwrap());
+    assert!(Parser::new().max_depth(depth).parse(input).is_ok());
+    let err = Parser::new().max_depth(depth - 1).parse(input).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::DepthExceeded);
+}
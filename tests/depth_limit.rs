@@ -0,0 +1,48 @@
+//! 嵌套层数的限制
+//!
+//! 递归的解析器在进入新的一层之前检查 max_depth，
+//! 嵌套过深的输入返回 DepthExceeded，而不是耗尽调用栈。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{DEFAULT_MAX_DEPTH, ErrorKind, Parser, parse, parse_spanned, validate};
+
+/// depth 层嵌套的空数组
+fn nested(depth: usize) -> String {
+    format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn pathological_nesting_is_a_clean_error() {
+    let err = parse(&"[".repeat(100_000)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::DepthExceeded);
+    assert_eq!(err.offset, DEFAULT_MAX_DEPTH + 1);  // 在第 129 层开始之前停下
+    let input = format!("{}{}", "{\"a\":".repeat(100_000), "}".repeat(100_000));
+    assert_eq!(parse(&input).unwrap_err().kind, ErrorKind::DepthExceeded);
+}
+
+#[test]
+fn pathological_nesting_in_other_entry_points() {
+    let arrays = "[".repeat(100_000);
+    let objects = "{\"a\":".repeat(100_000);
+    for input in [&arrays, &objects] {
+        assert_eq!(validate(input).unwrap_err().kind, ErrorKind::DepthExceeded);
+        assert_eq!(parse_spanned(input).unwrap_err().kind, ErrorKind::DepthExceeded);
+        assert_eq!(Parser::new().parse_native(input).unwrap_err().kind, ErrorKind::DepthExceeded);
+    }
+}
+
+#[test]
+fn default_limit() {
+    assert!(parse(&nested(DEFAULT_MAX_DEPTH)).is_ok());
+    assert_eq!(parse(&nested(DEFAULT_MAX_DEPTH + 1)).unwrap_err().kind, ErrorKind::DepthExceeded);
+}
+
+#[test]
+fn custom_limit() {
+    let parser = Parser::new().max_depth(2);
+    assert!(parser.parse("[[1], {\"a\": 2}]").is_ok());
+    let err = parser.parse("[[[1]]]").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::DepthExceeded);
+    assert_eq!(err.offset, 3);  // 第三层开始括号之后的位置
+}
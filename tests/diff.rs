@@ -0,0 +1,50 @@
+//! diff
+//!
+//! 比较两个嵌套的对象，检查每处差异的种类、位置和顺序。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{Change, diff};
+use serde_json::json;
+
+#[test]
+fn nested_objects() {
+    let a = json!({
+        "name": "a",
+        "server": {"host": "x", "ports": [80, 443, 8080], "tls": {"on": false}},
+        "a/b": 1,
+        "gone": null
+    });
+    let b = json!({
+        "name": "a",
+        "server": {"host": "y", "ports": [80, 8443], "tls": {}, "debug": true},
+        "a/b": {"c": 1},
+        "new": [1]
+    });
+    let expected = vec![
+        Change::Replaced { path: "/server/host".into(), before: json!("x"), after: json!("y") },
+        Change::Replaced { path: "/server/ports/1".into(), before: json!(443), after: json!(8443) },
+        Change::Removed { path: "/server/ports/2".into(), value: json!(8080) },
+        Change::Removed { path: "/server/tls/on".into(), value: json!(false) },
+        Change::Added { path: "/server/debug".into(), value: json!(true) },
+        Change::Replaced { path: "/a~1b".into(), before: json!(1), after: json!({"c": 1}) },  // 键按 JSON Pointer 转义
+        Change::Removed { path: "/gone".into(), value: json!(null) },
+        Change::Added { path: "/new".into(), value: json!([1]) },
+    ];
+    assert_eq!(diff(&a, &b), expected);
+}
+
+#[test]
+fn identical_and_reversed() {
+    let a = json!({"x": {"y": [1, {"z": 2}]}});
+    assert!(diff(&a, &a.clone()).is_empty());
+    let b = json!({"x": {"y": [1, {"z": 3}, 4]}});
+    let paths: Vec<_> = diff(&a, &b).iter().map(|c| c.path().to_string()).collect();
+    assert_eq!(paths, ["/x/y/1/z", "/x/y/2"]);
+    assert_eq!(diff(&b, &a)[1], Change::Removed { path: "/x/y/2".into(), value: json!(4) });  // 反过来比较，新增变成删除
+}
+
+#[test]
+fn root_replaced() {
+    assert_eq!(diff(&json!([1]), &json!({})), [Change::Replaced { path: "".into(), before: json!([1]), after: json!({}) }]);
+}
@@ -0,0 +1,45 @@
+//! 重复的键
+//!
+//! parse_strict 和 DuplicateKeys::Error 拒绝重复的键，默认的 parse 和 Parser 按后出现的值覆盖，
+//! DuplicateKeys::FirstWins 保留先出现的值。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{DuplicateKeys, ErrorKind, Parser, parse, parse_strict};
+use serde_json::json;
+
+#[test]
+fn strict_rejects_duplicate_keys() {
+    let err = parse_strict(r#"{"a":1,"a":2}"#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::DuplicateKey("a".to_string()));
+    assert_eq!((err.line, err.column, err.offset), (1, 8, 7));  // 第二次出现的键
+    let err = parse_strict(r#"{"x": {"b": 1, "c": 2, "b": 3}}"#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::DuplicateKey("b".to_string()));
+    assert_eq!(err.offset, 23);  // 嵌套对象里的重复键同样报错
+    assert_eq!(parse_strict(r#"{"a":1,"b":{"a":2}}"#).unwrap(), json!({"a": 1, "b": {"a": 2}}));  // 不同对象里的同名键不算重复
+}
+
+#[test]
+fn default_is_last_wins() {
+    assert_eq!(parse(r#"{"a":1,"a":2}"#).unwrap(), json!({"a": 2}));
+    assert_eq!(Parser::new().parse(r#"{"a":1,"a":2}"#).unwrap(), json!({"a": 2}));
+    let last_wins = Parser::new().duplicate_keys(DuplicateKeys::LastWins);
+    assert_eq!(last_wins.parse(r#"{"a":1,"a":2}"#).unwrap(), json!({"a": 2}));
+}
+
+#[test]
+fn first_wins() {
+    let first_wins = Parser::new().duplicate_keys(DuplicateKeys::FirstWins);
+    assert_eq!(first_wins.parse(r#"{"a":1,"a":2}"#).unwrap(), json!({"a": 1}));
+    let value = first_wins.parse(r#"{"a":1,"b":2,"a":{"x":3}}"#).unwrap();
+    assert_eq!(value, json!({"a": 1, "b": 2}));
+    assert_eq!(value.as_object().unwrap().keys().collect::<Vec<_>>(), ["a", "b"]);  // 键的位置也是第一次出现时的位置
+}
+
+#[test]
+fn error_policy() {
+    let error = Parser::new().duplicate_keys(DuplicateKeys::Error);
+    let err = error.parse(r#"{"a":1,"a":2}"#).unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::DuplicateKey("a".to_string()), 7));  // 与 parse_strict 相同
+    assert_eq!(error.parse(r#"{"a":1,"b":2}"#).unwrap(), json!({"a": 1, "b": 2}));
+}
@@ -0,0 +1,51 @@
+//! 空输入
+//!
+//! 空字符串和只有空白的输入返回专门的 EmptyInput 错误，而不是一般的 UnexpectedEof，
+//! 打开 empty_as_null 时解析为 null。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, json, parse, parse_iterative, validate};
+use serde_json::Value;
+
+#[test]
+fn empty_input() {
+    for input in ["", "   ", "\n\n"] {
+        assert_eq!(parse(input).unwrap_err().kind, ErrorKind::EmptyInput, "{:?}", input);
+        assert_eq!(parse_iterative(input).unwrap_err().kind, ErrorKind::EmptyInput, "{:?}", input);
+    }
+}
+
+#[test]
+fn validate_empty_input() {
+    for input in ["", "   ", "\n\n"] {
+        let err = validate(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (ErrorKind::EmptyInput, input.len()), "{:?}", input);  // 与 parse 相同
+    }
+    assert_eq!(validate("\u{FEFF}").unwrap_err().kind, ErrorKind::EmptyInput);
+    assert_eq!(validate("[").unwrap_err().kind, ErrorKind::UnterminatedArray);
+}
+
+#[test]
+fn empty_input_position() {
+    let err = parse("\n\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.offset), (3, 1, 2));  // 指向输入的末尾
+}
+
+#[test]
+fn incomplete_input_is_not_empty() {
+    assert_eq!(parse("[").unwrap_err().kind, ErrorKind::UnterminatedArray);
+}
+
+#[test]
+fn empty_as_null() {
+    let lenient = Parser::new().empty_as_null(true);
+    let strict = Parser::new().empty_as_null(false);
+    for input in ["", "   ", "\n\n"] {
+        assert_eq!(lenient.parse(input).unwrap(), Value::Null, "{:?}", input);
+        assert_eq!(lenient.parse_native(input).unwrap(), json::Value::Null, "{:?}", input);
+        assert_eq!(strict.parse(input).unwrap_err().kind, ErrorKind::EmptyInput, "{:?}", input);
+    }
+    assert_eq!(lenient.parse(" [1] ").unwrap(), serde_json::json!([1]));  // 非空输入不受影响
+    assert_eq!(lenient.parse("[").unwrap_err().kind, ErrorKind::UnterminatedArray);
+}
@@ -0,0 +1,72 @@
+//! 字符串中的转义
+//!
+//! \uXXXX 转义和 UTF-16 代理对的解码；无法识别的转义默认报错，
+//! invalid_escape 可以改为保留原字符或替换为 U+FFFD。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, InvalidEscapePolicy, Parser, parse};
+use serde_json::{Value, json};
+
+fn parse_with(policy: InvalidEscapePolicy, input: &str) -> Value {
+    Parser::new().invalid_escape(policy).parse(input).unwrap()
+}
+
+#[test]
+fn error_policy() {
+    let err = parse(r#""\x41""#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidEscape);
+    assert_eq!(err.offset, 1);  // 位置指向反斜杠
+    let err = Parser::new().invalid_escape(InvalidEscapePolicy::Error).parse(r#""\x41""#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidEscape);
+}
+
+#[test]
+fn keep_literal_policy() {
+    assert_eq!(parse_with(InvalidEscapePolicy::KeepLiteral, r#""\x41""#), json!("x41"));
+    assert_eq!(parse_with(InvalidEscapePolicy::KeepLiteral, r#"["\é", "\n"]"#), json!(["é", "\n"]));  // 合法的转义不受影响
+}
+
+#[test]
+fn replace_policy() {
+    assert_eq!(parse_with(InvalidEscapePolicy::Replace, r#""\x41""#), json!("\u{FFFD}41"));
+    assert_eq!(parse_with(InvalidEscapePolicy::Replace, r#"{"\q": "A"}"#), json!({"\u{FFFD}": "A"}));  // 键同样适用
+}
+
+#[test]
+fn malformed_and_truncated_escapes_still_fail() {
+    for policy in [InvalidEscapePolicy::KeepLiteral, InvalidEscapePolicy::Replace] {
+        let err = Parser::new().invalid_escape(policy).parse(r#""\u12x4""#).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidEscape);
+        let err = Parser::new().invalid_escape(policy).parse(r#""\x"#).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnterminatedString);
+    }
+}
+
+#[test]
+fn control_chars_after_backslash_still_fail() {
+    for policy in [InvalidEscapePolicy::KeepLiteral, InvalidEscapePolicy::Replace] {
+        let parser = Parser::new().invalid_escape(policy);
+        for input in ["\"\\\u{1}\"", "\"a\\\nb\"", "\"\\\t\"", "{\"\\\u{1f}\": 1}"] {
+            let err = parser.parse(input).unwrap_err();
+            assert_eq!((err.kind, err.offset), (ErrorKind::InvalidEscape, input.find('\\').unwrap()), "{:?}", input);  // 位置指向反斜杠
+            assert_eq!(parser.parse_native(input).unwrap_err().kind, ErrorKind::InvalidEscape, "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn surrogate_pairs() {
+    assert_eq!(parse(r#""\uD83D\uDE00""#).unwrap(), json!("😀"));
+    assert_eq!(parse(r#""a\ud83d\ude00b""#).unwrap(), json!("a😀b"));  // 十六进制数字不区分大小写
+    assert_eq!(parse(r#""\u00e9""#).unwrap(), json!("é"));  // 基本多文种平面的字符
+}
+
+#[test]
+fn lone_surrogates() {
+    for input in [r#""\uD83D""#, r#""\uD83Dx""#, r#""\uD83D\u0041""#, r#""\uDE00""#] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidEscape, "{}", input);
+        assert_eq!(err.offset, 1, "{}", input);  // 位置指向反斜杠
+    }
+}
@@ -0,0 +1,62 @@
+//! flatten 和 unflatten
+//!
+//! 展开再还原应该得到原来的值；还原时数字片段只在能接上已有数组时才作为下标，
+//! 巨大的下标按对象的键处理，不会分配巨大的数组。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{flatten, flatten_with_separator, unflatten, unflatten_with_separator};
+use serde_json::{Map, Value, json};
+
+/// 由键值对构造展开的结果
+fn flat(pairs: &[(&str, Value)]) -> Map<String, Value> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+}
+
+#[test]
+fn round_trip() {
+    let v = json!({
+        "name": "x",
+        "address": {"city": "New York", "zip": null},
+        "courses": ["Math", {"title": "Science", "tags": ["a", "b"]}],
+        "empty_array": [],
+        "empty_object": {},
+        "nested": [[1, 2], [3]]
+    });
+    assert_eq!(unflatten(&flatten(&v)), v);
+    assert_eq!(unflatten(&flatten(&json!(5))), json!(5));  // 标量得到键为空字符串的结果
+    assert_eq!(unflatten(&flatten(&json!([]))), json!([]));
+    assert_eq!(unflatten(&Map::new()), json!({}));
+}
+
+#[test]
+fn custom_separator() {
+    let v = json!({"a.b": {"c": [true]}});  // 键里有 "."，换一个分隔符
+    let flattened = flatten_with_separator(&v, "__");
+    assert_eq!(flattened, flat(&[("a.b__c__0", json!(true))]));
+    assert_eq!(unflatten_with_separator(&flattened, "__"), v);
+    assert_ne!(unflatten(&flatten(&v)), v);  // 默认分隔符无法还原
+}
+
+#[test]
+fn array_indices() {
+    let v = json!({"courses": ["Math", "Science", "History"]});
+    let flattened = flatten(&v);
+    assert_eq!(flattened.keys().collect::<Vec<_>>(), ["courses.0", "courses.1", "courses.2"]);
+    assert_eq!(unflatten(&flattened), v);
+    assert_eq!(unflatten(&flat(&[("a.1", json!(true))])), json!({"a": {"1": true}}));  // 数组必须从下标 0 开始
+    let sparse = flat(&[("a.0", json!(1)), ("a.5", json!(2))]);
+    assert_eq!(unflatten(&sparse), json!({"a": {"0": 1, "5": 2}}));  // 已有的元素以下标为键保留
+    assert_eq!(unflatten(&flat(&[("a.x", json!(1)), ("a.0", json!(2))])), json!({"a": {"x": 1, "0": 2}}));
+}
+
+#[test]
+fn huge_indices() {
+    let max = flat(&[("a.18446744073709551615", json!(1))]);
+    assert_eq!(unflatten(&max), json!({"a": {"18446744073709551615": 1}}));
+    assert_eq!(unflatten(&flat(&[("a.4000000000", json!(1))])), json!({"a": {"4000000000": 1}}));
+    let large = flat(&[("a.0", json!(0)), ("a.4000000000", json!(1))]);
+    assert_eq!(unflatten(&large), json!({"a": {"0": 0, "4000000000": 1}}));
+    let overflow = flat(&[("a.99999999999999999999999", json!(1))]);
+    assert_eq!(unflatten(&overflow), json!({"a": {"99999999999999999999999": 1}}));
+}
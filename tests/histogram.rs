@@ -0,0 +1,40 @@
+//! type_histogram
+//!
+//! 在示例文档和一些边界情况上检查每种类型的计数。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, TypeCounts, parse, type_histogram};
+use serde_json::json;
+
+#[test]
+fn sample_document() {
+    let input = include_str!("sample.json");
+    let counts = type_histogram(&parse(input).unwrap());
+    let expected = TypeCounts { nulls: 1, bools: 2, numbers: 11, strings: 16, arrays: 6, objects: 11 };
+    assert_eq!(counts, expected);
+    assert_eq!(counts.total(), 47);
+    assert!(Parser::new().max_elements(47).parse(input).is_ok());  // 与 max_elements 的计数方式相同
+    assert_eq!(Parser::new().max_elements(46).parse(input).unwrap_err().kind, ErrorKind::ResourceLimit);
+}
+
+#[test]
+fn scalar_root() {
+    assert_eq!(type_histogram(&json!(1)), TypeCounts { numbers: 1, ..TypeCounts::default() });
+    assert_eq!(type_histogram(&json!("s")), TypeCounts { strings: 1, ..TypeCounts::default() });
+    assert_eq!(type_histogram(&json!(null)).total(), 1);
+}
+
+#[test]
+fn empty_containers() {
+    assert_eq!(type_histogram(&json!([])), TypeCounts { arrays: 1, ..TypeCounts::default() });
+    assert_eq!(type_histogram(&json!({})), TypeCounts { objects: 1, ..TypeCounts::default() });
+    assert_eq!(type_histogram(&json!([[], {}])), TypeCounts { arrays: 2, objects: 1, ..TypeCounts::default() });
+}
+
+#[test]
+fn counts_per_type() {
+    let value = json!({"a": [1, 2.5, "x", null, true, false], "b": {"c": "d"}});
+    let expected = TypeCounts { nulls: 1, bools: 2, numbers: 2, strings: 2, arrays: 1, objects: 2 };
+    assert_eq!(type_histogram(&value), expected);  // 对象的键不计入 strings
+}
@@ -0,0 +1,32 @@
+//! 截断的输入
+//!
+//! ParseError::incomplete 区分输入在值的中间结束和输入本身有错：
+//! 前者再多一些输入就可能成功，StreamParser 据此决定继续等待还是报错。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{Parser, StreamParser, parse};
+
+#[test]
+fn truncated_input_is_incomplete() {
+    for input in ["[", "[1", "[1,", "{", "{\"a\"", "{\"a\":", "{\"a\":1,", "{\"a\":1,\"b\"", "[tr", "[1,-"] {
+        assert!(parse(input).unwrap_err().incomplete, "{:?}", input);
+    }
+}
+
+#[test]
+fn stray_commas_are_not_incomplete() {
+    for input in ["[,", "{,", "[1,,", "{\"a\":1,,", "[ , ", "{,\"a\""] {
+        assert!(!parse(input).unwrap_err().incomplete, "{:?}", input);
+    }
+    let lenient = Parser::new().allow_trailing_comma(true);
+    assert!(lenient.parse("[1,").unwrap_err().incomplete);
+    assert!(!lenient.parse("[1,,").unwrap_err().incomplete);
+}
+
+#[test]
+fn stream_reports_stray_commas() {
+    assert!(StreamParser::new().feed("[1,,").is_err());
+    assert!(StreamParser::new().feed("[,").is_err());
+    assert_eq!(StreamParser::new().feed("[1,").unwrap(), None);  // 还在等待下一个元素
+}
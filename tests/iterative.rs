@@ -0,0 +1,40 @@
+//! 非递归的解析器
+//!
+//! parse_iterative 的嵌套层数只受内存限制，结果与 parse 完全相同。
+
+#![cfg(feature = "serde_json")]
+
+use std::mem::ManuallyDrop;
+
+use json_parser::{parse, parse_iterative, values_equal};
+use serde_json::{Map, Value, json};
+
+#[test]
+fn ten_thousand_levels() {
+    let input = format!("{}1{}", "[{\"k\":".repeat(5_000), "}]".repeat(5_000));
+    // serde_json::Value 的析构是递归的，这里不释放结果，避免测试本身栈溢出
+    let value = ManuallyDrop::new(parse_iterative(&input).unwrap());
+    let mut expected = json!(1);
+    for _ in 0..5_000 {
+        let object = Map::from_iter([("k".to_string(), expected)]);
+        expected = Value::Array(vec![Value::Object(object)]);  // 从里向外构造同样的结构；json! 会递归序列化
+    }
+    let expected = ManuallyDrop::new(expected);
+    assert!(values_equal(&value, &expected));  // 递归的 == 在这么深的值上可能栈溢出
+}
+
+#[test]
+fn same_output_as_parse() {
+    for input in [
+        "null",
+        " [1, -2.5, 1e3, \"a\\nb\", true, false, null] ",
+        r#"{"b": {"c": [[], {}]}, "a": [{"x": 1}, {"x": 2}], "s": "é"}"#,
+        include_str!("sample.json"),
+    ] {
+        let expected: Value = parse(input).unwrap();
+        assert_eq!(parse_iterative(input).unwrap(), expected, "{}", input);
+    }
+    for input in ["[1, 2", "{\"a\" 1}", "[1] 2", ""] {
+        assert_eq!(parse_iterative(input).unwrap_err().kind, parse(input).unwrap_err().kind, "{}", input);
+    }
+}
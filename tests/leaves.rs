@@ -0,0 +1,31 @@
+//! leaves
+//!
+//! 在示例文档上检查叶子的个数、路径和顺序，以及路径可以交给 pointer 取回同一个值。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{leaves, parse, pointer};
+use serde_json::{Value, json};
+
+#[test]
+fn sample_document() {
+    let v = parse(include_str!("sample.json")).unwrap();
+    let all = leaves(&v);
+    assert_eq!(all.len(), 30);
+    assert!(all.contains(&("/address/city".to_string(), &json!("New York"))));
+    assert_eq!(all[0], ("/name".to_string(), &json!("John\nDoe")));  // 按文档顺序
+    assert_eq!(all[3], ("/courses/0".to_string(), &json!("Math")));
+    assert_eq!(all[29], ("/complex_value/object/key4".to_string(), &json!("value4")));
+    assert!(all.iter().all(|(path, _)| !path.starts_with("/empty_")));  // 空容器没有叶子
+    for (path, leaf) in &all {
+        assert_eq!(pointer(&v, path), Some(*leaf), "{}", path);
+    }
+}
+
+#[test]
+fn edge_cases() {
+    assert_eq!(leaves(&json!(5)), [(String::new(), &json!(5))]);
+    assert!(leaves(&json!({"a": [], "b": {}})).is_empty());
+    let v = json!({"a/b": {"m~n": [null]}});
+    assert_eq!(leaves(&v), [("/a~1b/m~0n/0".to_string(), &Value::Null)]);
+}
@@ -0,0 +1,76 @@
+//! 资源限制
+//!
+//! max_elements、max_number_len、max_object_entries 等选项限制文档的规模，超出时返回 ResourceLimit，而不是耗尽内存。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser};
+use serde_json::json;
+
+/// n 个元素的扁平数组
+fn flat_array(n: usize) -> String {
+    format!("[{}0]", "0,".repeat(n - 1))
+}
+
+#[test]
+fn max_elements_huge_flat_array() {
+    let parser = Parser::new().max_elements(1000);
+    let err = parser.parse(&flat_array(1_000_000)).unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::ResourceLimit, 2001));  // 第 1001 个元素
+    assert_eq!(parser.parse_native(&flat_array(1_000_000)).unwrap_err().kind, ErrorKind::ResourceLimit);
+}
+
+#[test]
+fn max_elements_counts_every_value() {
+    let parser = Parser::new().max_elements(1000);
+    assert!(parser.parse(&flat_array(999)).is_ok());  // 999 个元素加上数组本身
+    assert_eq!(parser.parse(&flat_array(1000)).unwrap_err().kind, ErrorKind::ResourceLimit);
+    let parser = Parser::new().max_elements(4);
+    assert_eq!(parser.parse("[1, [2]]").unwrap(), json!([1, [2]]));
+    assert_eq!(parser.parse("[1, [2, 3]]").unwrap_err().kind, ErrorKind::ResourceLimit);
+    let parser = Parser::new().max_elements(3);
+    assert_eq!(parser.parse(r#"{"a": [2]}"#).unwrap(), json!({"a": [2]}));  // 键不算值
+}
+
+#[test]
+fn max_number_len_million_digits() {
+    let parser = Parser::new().max_number_len(100);
+    let digits = "9".repeat(1_000_000);
+    let err = parser.parse(&digits).unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::ResourceLimit, 0));  // 位置指向数字的开头
+    let err = parser.parse(&format!("[1, -{}]", digits)).unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::ResourceLimit, 4));
+    assert_eq!(parser.parse_native(&digits).unwrap_err().kind, ErrorKind::ResourceLimit);
+}
+
+#[test]
+fn max_number_len_counts_every_character() {
+    let parser = Parser::new().max_number_len(100);
+    assert!(parser.parse(&format!("0.{}", "1".repeat(98))).is_ok());  // 恰好 100 个字符
+    assert_eq!(parser.parse(&format!("0.{}", "1".repeat(99))).unwrap_err().kind, ErrorKind::ResourceLimit);
+    assert_eq!(Parser::new().max_number_len(3).parse("-1e5").unwrap_err().kind, ErrorKind::ResourceLimit);  // 符号和指数也算
+}
+
+#[test]
+fn max_object_and_array_entries() {
+    let parser = Parser::new().max_object_entries(2).max_array_entries(3);
+    assert!(parser.parse(r#"[{"a":1,"b":2},[1,2,3]]"#).is_ok());  // 每个容器恰好到达上限
+    for (input, offset) in [
+        (r#"{"a":1,"b":2,"c":3}"#, 13),  // 第三个键
+        (r#"{"a":1,"a":2,"a":3}"#, 13),  // 重复的键也各算一个成员
+        ("[1,2,3,4]", 7),  // 第四个元素
+        (r#"{"x":[1,2,3,4]}"#, 12),
+        (r#"[[1,2],{"a":1,"b":2,"c":3}]"#, 20),
+    ] {
+        let err = parser.parse(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (ErrorKind::ResourceLimit, offset), "{}", input);
+        assert_eq!(parser.parse_native(input).unwrap_err().offset, offset, "{}", input);
+    }
+}
+
+#[test]
+fn entry_limits_are_per_container() {
+    let parser = Parser::new().max_object_entries(1).max_array_entries(1);
+    assert_eq!(parser.parse(r#"[{"a":[{"b":[1]}]}]"#).unwrap(), json!([{"a": [{"b": [1]}]}]));  // 嵌套的容器分别计数
+    assert_eq!(parser.parse(r#"{"a":[[1,2]]}"#).unwrap_err().kind, ErrorKind::ResourceLimit);
+}
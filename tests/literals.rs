@@ -0,0 +1,31 @@
+//! 大小写不同的字面量
+//!
+//! 默认只接受小写的 true、false 和 null，打开 case_insensitive_literals 后也接受 TRUE、False、NULL 等写法。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, ParserConfig, parse};
+use serde_json::json;
+
+#[test]
+fn strict_mode_rejects_other_cases() {
+    for input in ["TRUE", "False", "NULL"] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnexpectedChar, "{}", input);
+        assert_eq!(err.offset, 0);
+    }
+    let lenient = Parser::from(ParserConfig::lenient());  // 宽松配置本身不会打开这个选项
+    assert!(lenient.parse("TRUE").is_err());
+}
+
+#[test]
+fn case_insensitive_mode_accepts_other_cases() {
+    let parser = Parser::new().case_insensitive_literals(true);
+    assert_eq!(parser.parse("TRUE").unwrap(), json!(true));
+    assert_eq!(parser.parse("False").unwrap(), json!(false));
+    assert_eq!(parser.parse("NULL").unwrap(), json!(null));
+    assert_eq!(parser.parse(r#"{"a": [True, fALSE, Null, null]}"#).unwrap(), json!({"a": [true, false, null, null]}));
+    assert_eq!(parser.parse_native(" NULL ").unwrap(), json_parser::json::Value::Null);
+    assert!(parser.parse("Nul").is_err());  // 字面量仍然必须完整
+    assert!(parser.parse("TRUEx").is_err());
+}
@@ -0,0 +1,30 @@
+//! 经过 f64 会改变的数字
+//!
+//! 打开 reject_lossy_numbers 后，转换为 f64 无法还原的数字返回 LossyNumber。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, parse};
+use serde_json::json;
+
+#[test]
+fn two_to_the_53_plus_one() {
+    let parser = Parser::new().reject_lossy_numbers(true);
+    let err = parser.parse("9007199254740993").unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::LossyNumber, 0));
+    let err = parser.parse("[1, -9007199254740993]").unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::LossyNumber, 4));  // 位置指向这个数字
+    assert_eq!(parser.parse_native("9007199254740993").unwrap_err().kind, ErrorKind::LossyNumber);
+    assert_eq!(parse("9007199254740993").unwrap(), json!(9007199254740993u64));  // 默认关闭，按整数精确保存
+}
+
+#[test]
+fn exact_numbers_are_accepted() {
+    let parser = Parser::new().reject_lossy_numbers(true);
+    for input in ["9007199254740992", "0.1", "1.5", "2e53", "1e300", "-0"] {
+        assert!(parser.parse(input).is_ok(), "{}", input);
+    }
+    for input in ["18446744073709551615", "123456789012345678901234567890", "1e400"] {
+        assert_eq!(parser.parse(input).unwrap_err().kind, ErrorKind::LossyNumber, "{}", input);
+    }
+}
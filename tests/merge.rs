@@ -0,0 +1,95 @@
+//! deep_merge 和 merge_patch
+//!
+//! 在嵌套的对象和数组上分别检查 Replace 和 Concat 两种数组处理方式；
+//! merge_patch 用 RFC 7386 附录 A 中的例子逐条检查。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ArrayStrategy, deep_merge, merge_patch, parse};
+use serde_json::{Value, json};
+
+/// 基础配置
+fn base() -> Value {
+    json!({
+        "server": {"ports": [80], "tls": {"enabled": false, "ciphers": ["a"]}},
+        "plugins": ["auth"],
+        "name": "base"
+    })
+}
+
+/// 覆盖配置
+fn overlay() -> Value {
+    json!({
+        "server": {"ports": [443], "tls": {"enabled": true, "ciphers": ["b", "c"]}, "host": "x"},
+        "plugins": ["log"],
+        "name": null
+    })
+}
+
+#[test]
+fn deep_merge_replace() {
+    let mut value = base();
+    deep_merge(&mut value, overlay(), ArrayStrategy::Replace);
+    let expected = json!({
+        "server": {"ports": [443], "tls": {"enabled": true, "ciphers": ["b", "c"]}, "host": "x"},
+        "plugins": ["log"],
+        "name": null
+    });
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn deep_merge_concat() {
+    let mut value = base();
+    deep_merge(&mut value, overlay(), ArrayStrategy::Concat);
+    let expected = json!({
+        "server": {"ports": [80, 443], "tls": {"enabled": true, "ciphers": ["a", "b", "c"]}, "host": "x"},
+        "plugins": ["auth", "log"],
+        "name": null  // null 覆盖原来的值，而不是删除
+    });
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn deep_merge_type_mismatch() {
+    for strategy in [ArrayStrategy::Replace, ArrayStrategy::Concat] {
+        let mut value = json!({"a": [1], "b": {"c": 1}});
+        deep_merge(&mut value, json!({"a": {"x": 1}, "b": [2]}), strategy);
+        assert_eq!(value, json!({"a": {"x": 1}, "b": [2]}));  // 类型不同时整体替换
+        let mut value = json!([[1], [2]]);
+        deep_merge(&mut value, json!([[3]]), strategy);
+        let expected = match strategy {
+            ArrayStrategy::Replace => json!([[3]]),
+            ArrayStrategy::Concat => json!([[1], [2], [3]]),  // 只拼接外层，不按下标合并元素
+        };
+        assert_eq!(value, expected);
+    }
+}
+
+/// RFC 7386 附录 A：(原文档, 补丁, 结果)
+const RFC_7386_EXAMPLES: [(&str, &str, &str); 15] = [
+    (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+    (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+    (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+    (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+    (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+    (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+    (r#"{"a":{"b":"c"}}"#, r#"{"a":{"b":"d","c":null}}"#, r#"{"a":{"b":"d"}}"#),
+    (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+    (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+    (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+    (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+    (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+    (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+    (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+    (r#"{}"#, r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#),
+];
+
+#[test]
+fn merge_patch_rfc_examples() {
+    for (target, patch, expected) in RFC_7386_EXAMPLES {
+        let mut value = parse(target).unwrap();
+        merge_patch(&mut value, &parse(patch).unwrap());
+        assert_eq!(value, parse(expected).unwrap(), "{} + {}", target, patch);
+    }
+}
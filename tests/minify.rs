@@ -0,0 +1,27 @@
+//! minify
+//!
+//! 去掉字符串之外的空白，字符串内部的空白和转义原样保留。
+
+use json_parser::{ErrorKind, minify, validate};
+
+#[test]
+fn whitespace_inside_strings() {
+    let input = " { \"a b\" : [ \" x\\t y \" , 1 ] } ";
+    assert_eq!(minify(input).unwrap(), r#"{"a b":[" x\t y ",1]}"#);
+    assert_eq!(minify(r#" "  \"  " "#).unwrap(), r#""  \"  ""#);  // 转义的引号不会结束字符串
+    assert_eq!(minify(r#"[ "  \n" ]"#).unwrap(), r#"["  \n"]"#);  // 转义保持原文，不会被还原
+}
+
+#[test]
+fn whitespace_outside_strings() {
+    assert_eq!(minify("\n[\n]\n").unwrap(), "[]");
+    assert_eq!(minify("\t{ \"k\" :\r\n\ttrue }").unwrap(), r#"{"k":true}"#);
+    let minified = minify(" [ 1 , { \"a\" : null } ] ").unwrap();
+    assert_eq!(minified, r#"[1,{"a":null}]"#);
+    assert!(validate(&minified).is_ok());
+}
+
+#[test]
+fn invalid_input() {
+    assert_eq!(minify("[1,").unwrap_err().kind, ErrorKind::UnterminatedArray);  // 与 validate 相同的错误
+}
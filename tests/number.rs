@@ -0,0 +1,43 @@
+//! 数字分类
+//!
+//! 同一份混合了整数和浮点数的输入，分别用 serde_json::Value 上的函数
+//! 和 json::Value 上的方法检查，两者的结果应该一致。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{Parser, as_exact_i64, is_f64, is_i64, is_u64, parse};
+
+const MIXED: &str = r#"[0, -7, 3.0, 3.5, 1e2, -0.0, 18446744073709551615, 1e19, -9223372036854775808, "1", null]"#;
+
+/// 每个元素的期望结果：(is_i64, is_u64, is_f64, as_exact_i64)
+const EXPECTED: [(bool, bool, bool, Option<i64>); 11] = [
+    (true, true, false, Some(0)),
+    (true, false, false, Some(-7)),
+    (false, false, true, Some(3)),  // 3.0 按浮点数保存，但恰好是整数
+    (false, false, true, None),
+    (false, false, true, Some(100)),
+    (false, false, true, Some(0)),
+    (false, true, false, None),  // 超出 i64，只能用 u64 表示
+    (false, false, true, None),  // 超出 i64 范围的浮点数
+    (true, false, false, Some(i64::MIN)),
+    (false, false, false, None),  // 字符串不是数字
+    (false, false, false, None),
+];
+
+#[test]
+fn classify_serde_values() {
+    let value = parse(MIXED).unwrap();
+    let items = value.as_array().unwrap();
+    for (item, expected) in items.iter().zip(EXPECTED) {
+        assert_eq!((is_i64(item), is_u64(item), is_f64(item), as_exact_i64(item)), expected, "{}", item);
+    }
+}
+
+#[test]
+fn classify_native_values() {
+    let value = Parser::new().parse_native(MIXED).unwrap();
+    let items = value.as_array().unwrap();
+    for (item, expected) in items.iter().zip(EXPECTED) {
+        assert_eq!((item.is_i64(), item.is_u64(), item.is_f64(), item.as_exact_i64()), expected, "{:?}", item);
+    }
+}
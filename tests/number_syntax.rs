@@ -0,0 +1,41 @@
+//! 数字的语法
+//!
+//! 按 RFC 8259 的数字文法检查哪些写法可以解析、哪些返回 InvalidNumber。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, parse};
+
+/// 断言每个输入都解析失败并返回 InvalidNumber
+fn assert_invalid(inputs: &[&str]) {
+    for input in inputs {
+        assert_eq!(parse(input).unwrap_err().kind, ErrorKind::InvalidNumber, "{}", input);
+    }
+}
+
+#[test]
+fn leading_zeros() {
+    assert_eq!(parse("0").unwrap(), 0);
+    assert_eq!(parse("0.5").unwrap(), 0.5);
+    assert_eq!(parse("-0").unwrap(), 0);
+    assert_eq!(parse("10").unwrap(), 10);
+    assert_invalid(&["01", "-01", "007"]);  // 多位整数部分不能以 0 开头
+}
+
+#[test]
+fn fraction_and_exponent() {
+    assert_invalid(&["1e", "1e+", "1.", "1.e5"]);  // 小数点和指数后面至少要有一位数字
+    assert_eq!(parse("1e10").unwrap(), 1e10);
+    assert_eq!(parse("1E-5").unwrap(), 1e-5);
+    assert_eq!(parse("1.5e+3").unwrap(), 1500.0);
+}
+
+#[test]
+fn sign_and_decimal_point() {
+    assert_eq!(parse("+5").unwrap_err().kind, ErrorKind::UnexpectedChar);  // 数字不能以 + 开头
+    assert_eq!(parse(".5").unwrap_err().kind, ErrorKind::UnexpectedChar);  // 小数点前必须有整数部分
+    assert_eq!(parse("-.5").unwrap_err().kind, ErrorKind::UnexpectedChar);
+    assert_invalid(&["5."]);
+    assert_eq!(parse("-0").unwrap(), 0);
+    assert!(parse("-0").unwrap().is_i64());  // 按整数保存
+}
@@ -0,0 +1,97 @@
+//! JSON Patch
+//!
+//! 按 RFC 6902 逐个检查 add、remove、move、copy 和 test 操作，
+//! 以及失败的补丁不会修改文档。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{PatchError, apply_patch};
+use serde_json::{Value, json};
+
+/// 应用补丁并返回结果
+fn patched(mut doc: Value, patch: Value) -> Result<Value, PatchError> {
+    apply_patch(&mut doc, &patch).map(|()| doc)
+}
+
+#[test]
+fn add_to_end_of_array() {
+    let result = patched(json!({"a": [1, 2]}), json!([{"op": "add", "path": "/a/-", "value": 3}])).unwrap();
+    assert_eq!(result, json!({"a": [1, 2, 3]}));
+    let result = patched(json!([]), json!([{"op": "add", "path": "/-", "value": {"x": 1}}])).unwrap();
+    assert_eq!(result, json!([{"x": 1}]));
+    let result = patched(json!({"a": [1, 2]}), json!([{"op": "add", "path": "/a/1", "value": 9}])).unwrap();
+    assert_eq!(result, json!({"a": [1, 9, 2]}));  // 下标处插入，后面的元素后移
+    let err = patched(json!({"a": [1]}), json!([{"op": "add", "path": "/a/2", "value": 9}])).unwrap_err();
+    assert_eq!(err, PatchError::PathNotFound("/a/2".to_string()));
+}
+
+#[test]
+fn remove_missing_path() {
+    let err = patched(json!({"a": 1}), json!([{"op": "remove", "path": "/b"}])).unwrap_err();
+    assert_eq!(err, PatchError::PathNotFound("/b".to_string()));
+    let err = patched(json!({"a": [1]}), json!([{"op": "remove", "path": "/a/1"}])).unwrap_err();
+    assert_eq!(err, PatchError::PathNotFound("/a/1".to_string()));
+    let result = patched(json!({"a": 1, "b": 2, "c": 3}), json!([{"op": "remove", "path": "/b"}])).unwrap();
+    assert_eq!(result.as_object().unwrap().keys().collect::<Vec<_>>(), ["a", "c"]);  // 其余成员保持顺序
+}
+
+#[test]
+fn move_into_own_child() {
+    let patch = json!([{"op": "move", "from": "/a", "path": "/a/b"}]);
+    assert!(matches!(patched(json!({"a": {}}), patch).unwrap_err(), PatchError::InvalidOperation(_)));
+    let patch = json!([{"op": "move", "from": "/a", "path": "/ab"}]);
+    assert_eq!(patched(json!({"a": 1}), patch).unwrap(), json!({"ab": 1}));  // 只是前缀相同，不是子节点
+    let patch = json!([{"op": "move", "from": "/a/0", "path": "/a/-"}]);
+    assert_eq!(patched(json!({"a": [1, 2]}), patch).unwrap(), json!({"a": [2, 1]}));
+}
+
+#[test]
+fn copy() {
+    let patch = json!([{"op": "copy", "from": "/a", "path": "/b"}]);
+    assert_eq!(patched(json!({"a": {"x": [1]}}), patch).unwrap(), json!({"a": {"x": [1]}, "b": {"x": [1]}}));
+    let patch = json!([{"op": "copy", "from": "/a/0", "path": "/a/0"}]);
+    assert_eq!(patched(json!({"a": [7]}), patch).unwrap(), json!({"a": [7, 7]}));
+    let patch = json!([{"op": "copy", "from": "/missing", "path": "/b"}]);
+    assert_eq!(patched(json!({}), patch).unwrap_err(), PatchError::PathNotFound("/missing".to_string()));
+}
+
+#[test]
+fn test_failed() {
+    let err = patched(json!({"a": [1, 2]}), json!([{"op": "test", "path": "/a/0", "value": 5}])).unwrap_err();
+    assert_eq!(err, PatchError::TestFailed("/a/0".to_string()));
+    let err = patched(json!({"a": "1"}), json!([{"op": "test", "path": "/a", "value": 1}])).unwrap_err();
+    assert_eq!(err, PatchError::TestFailed("/a".to_string()));  // 类型不同
+    let patch = json!([{"op": "test", "path": "/a", "value": [1, 2, 3]}]);
+    assert_eq!(patched(json!({"a": [1, 2]}), patch).unwrap_err(), PatchError::TestFailed("/a".to_string()));
+}
+
+#[test]
+fn test_compares_numbers_by_value() {
+    let doc = json!({"n": 1, "list": [1, {"x": 2.0}], "big": 9007199254740993u64});
+    let patch = json!([
+        {"op": "test", "path": "/n", "value": 1.0},
+        {"op": "test", "path": "/list", "value": [1.0, {"x": 2}]},
+        {"op": "test", "path": "", "value": {"big": 9007199254740993u64, "list": [1, {"x": 2}], "n": 1e0}},
+    ]);
+    assert!(patched(doc.clone(), patch).is_ok());  // 成员顺序也不影响结果
+    let patch = json!([{"op": "test", "path": "/big", "value": 9007199254740992u64}]);
+    assert!(patched(doc.clone(), patch).is_err());  // 大整数不经过 f64 比较
+    let patch = json!([{"op": "test", "path": "/n", "value": 1.5}]);
+    assert!(patched(doc, patch).is_err());
+}
+
+#[test]
+fn failed_patch_leaves_doc_unchanged() {
+    let original = json!({"a": [1, 2], "b": {"c": true}});
+    let mut doc = original.clone();
+    let patch = json!([
+        {"op": "add", "path": "/a/-", "value": 3},
+        {"op": "remove", "path": "/b/c"},
+        {"op": "test", "path": "/a/0", "value": 5},
+    ]);
+    assert_eq!(apply_patch(&mut doc, &patch), Err(PatchError::TestFailed("/a/0".to_string())));
+    assert_eq!(doc, original);  // 前两个操作没有生效
+    let patch = json!([{"op": "replace", "path": "/a", "value": 0}, {"op": "remove", "path": "/x"}]);
+    assert!(apply_patch(&mut doc, &patch).is_err());
+    assert_eq!(doc, original);
+}
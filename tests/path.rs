@@ -0,0 +1,51 @@
+//! 按路径片段访问
+//!
+//! 在示例文档上用键和下标混合的路径检查 get 和 get_mut。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{PathSeg, get, get_mut, parse};
+use serde_json::{Value, json};
+
+/// 示例文档
+fn sample() -> Value {
+    parse(include_str!("sample.json")).unwrap()
+}
+
+#[test]
+fn get_mixed_paths() {
+    let v = sample();
+    assert_eq!(get(&v, &[PathSeg::Key("address"), PathSeg::Key("city")]), Some(&json!("New York")));
+    assert_eq!(get(&v, &[PathSeg::Key("courses"), PathSeg::Index(1)]), Some(&json!("Science")));
+    assert_eq!(get(&v, &[PathSeg::Key("nested_array"), PathSeg::Index(1), PathSeg::Index(2)]), Some(&json!(6)));
+    let path = [PathSeg::Key("complex_value"), PathSeg::Key("array"), PathSeg::Index(1), PathSeg::Key("key2")];
+    assert_eq!(get(&v, &path), Some(&json!("value2")));
+    assert_eq!(get(&v, &["grades".into(), "Math".into()]), Some(&json!(90)));  // 用 From 构造片段
+    assert_eq!(get(&v, &[PathSeg::Key("null_value")]), Some(&Value::Null));  // null 也是存在的值
+    assert_eq!(get(&v, &[]), Some(&v));
+}
+
+#[test]
+fn get_missing_or_mismatched() {
+    let v = sample();
+    assert_eq!(get(&v, &[PathSeg::Key("courses"), PathSeg::Index(3)]), None);  // 下标越界
+    assert_eq!(get(&v, &[PathSeg::Key("courses"), PathSeg::Key("0")]), None);  // 数组不能用键访问
+    assert_eq!(get(&v, &[PathSeg::Key("grades"), PathSeg::Index(0)]), None);  // 对象不能用下标访问
+    assert_eq!(get(&v, &[PathSeg::Key("age"), PathSeg::Index(0)]), None);  // 标量没有子节点
+    assert_eq!(get(&v, &[PathSeg::Key("empty_array"), PathSeg::Index(0)]), None);
+    assert_eq!(get(&v, &[PathSeg::Key("missing")]), None);
+}
+
+#[test]
+fn get_mut_modifies_in_place() {
+    let mut v = sample();
+    *get_mut(&mut v, &[PathSeg::Key("age")]).unwrap() = json!(31);
+    *get_mut(&mut v, &[PathSeg::Key("nested_array"), PathSeg::Index(0), PathSeg::Index(0)]).unwrap() = json!(0);
+    let path = [PathSeg::Key("complex_value"), PathSeg::Key("array"), PathSeg::Index(0)];
+    get_mut(&mut v, &path).unwrap().as_object_mut().unwrap().insert("added".into(), json!(true));
+    assert_eq!(v["age"], 31);
+    assert_eq!(v["nested_array"], json!([[0, 2, 3], [4, 5, 6]]));
+    assert_eq!(v["complex_value"]["array"][0], json!({"key1": "value1", "added": true}));
+    assert!(get_mut(&mut v, &[PathSeg::Key("courses"), PathSeg::Index(9)]).is_none());
+    assert!(get_mut(&mut v, &[PathSeg::Key("is_active"), PathSeg::Key("x")]).is_none());
+}
@@ -0,0 +1,71 @@
+//! pointer、has_pointer 和 keys_at
+//!
+//! 针对 tests/sample.json 示例文档中的 nested_object 检查嵌套对象的查询。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{has_pointer, keys_at, parse, pointer};
+use serde_json::{Value, json};
+
+/// 示例文档
+fn sample() -> Value {
+    parse(include_str!("sample.json")).unwrap()
+}
+
+#[test]
+fn has_pointer_nested_object() {
+    let v = sample();
+    assert!(has_pointer(&v, ""));
+    assert!(has_pointer(&v, "/nested_object"));
+    assert!(has_pointer(&v, "/nested_object/key1/subkey2"));
+    assert!(has_pointer(&v, "/null_value"));  // null 也是存在的值
+    assert!(has_pointer(&v, "/courses/2"));
+    assert!(!has_pointer(&v, "/nested_object/key3"));
+    assert!(!has_pointer(&v, "/nested_object/key1/subkey3"));  // subkey3 在 key2 里
+    assert!(!has_pointer(&v, "/nested_object/key1/subkey1/x"));  // 字符串没有子节点
+    assert!(!has_pointer(&v, "/courses/3"));
+    assert!(!has_pointer(&v, "nested_object"));  // 不以 '/' 开头
+}
+
+#[test]
+fn keys_at_nested_object() {
+    let v = sample();
+    assert_eq!(keys_at(&v, "/nested_object"), Some(vec!["key1", "key2"]));
+    assert_eq!(keys_at(&v, "/nested_object/key2"), Some(vec!["subkey3", "subkey4"]));
+    let top = [
+        "name", "age", "is_student", "courses", "address", "grades", "is_active", "balance", "null_value",
+        "escaped_string", "empty_array", "empty_object", "nested_array", "nested_object", "complex_value",
+    ];
+    assert_eq!(keys_at(&v, ""), Some(top.to_vec()));  // 文档中的顺序
+    assert_eq!(keys_at(&v, "/courses"), None);  // 数组没有键
+    assert_eq!(keys_at(&v, "/nested_object/key1/subkey1"), None);
+    assert_eq!(keys_at(&v, "/missing"), None);
+}
+
+#[test]
+fn pointer_escaped_tokens() {
+    let v = json!({"a/b": 1, "m~n": 2, "~1": 3, "": {"": 4}, "x": {"y/z": [5]}});
+    assert_eq!(pointer(&v, "/a~1b"), Some(&json!(1)));
+    assert_eq!(pointer(&v, "/m~0n"), Some(&json!(2)));
+    assert_eq!(pointer(&v, "/~01"), Some(&json!(3)));  // 先替换 ~1 再替换 ~0 会错误地得到 "/"
+    assert_eq!(pointer(&v, "//"), Some(&json!(4)));  // 空的引用片段是空字符串键
+    assert_eq!(pointer(&v, "/x/y~1z/0"), Some(&json!(5)));
+    assert_eq!(pointer(&v, "/a/b"), None);  // 未转义的 '/' 是分隔符
+    assert_eq!(pointer(&v, "/m~2n"), None);  // 无效的转义
+    assert_eq!(pointer(&v, "/m~"), None);
+}
+
+#[test]
+fn pointer_array_indices() {
+    let v = json!({"list": [10, 20], "empty": []});
+    assert_eq!(pointer(&v, "/list/0"), Some(&json!(10)));
+    assert_eq!(pointer(&v, "/list/1"), Some(&json!(20)));
+    assert_eq!(pointer(&v, "/list/2"), None);  // 下标越界
+    assert_eq!(pointer(&v, "/list/18446744073709551616"), None);  // 超出 usize 也只是越界
+    assert_eq!(pointer(&v, "/list/-"), None);  // 末尾之后的位置
+    assert_eq!(pointer(&v, "/list/01"), None);  // 前导零
+    assert_eq!(pointer(&v, "/list/-1"), None);
+    assert_eq!(pointer(&v, "/list/+1"), None);
+    assert_eq!(pointer(&v, "/empty/0"), None);
+    assert_eq!(pointer(&v, ""), Some(&v));
+}
@@ -0,0 +1,52 @@
+//! prune_nulls
+//!
+//! 用一份到处散落着 null 的文档检查两种模式下删除了哪些成员。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{parse, prune_nulls};
+use serde_json::json;
+
+/// null 出现在顶层成员、嵌套对象、数组元素以及数组里的对象中
+const SCATTERED: &str = r#"{
+    "id": 7,
+    "name": null,
+    "tags": [null, "a", {"x": null, "y": 0}],
+    "profile": {"email": null, "phone": {"home": null}},
+    "flags": [],
+    "meta": {},
+    "ok": false
+}"#;
+
+#[test]
+fn prune_keeps_empty_containers() {
+    let mut value = parse(SCATTERED).unwrap();
+    prune_nulls(&mut value, false);
+    let expected = json!({
+        "id": 7,
+        "tags": [null, "a", {"y": 0}],
+        "profile": {"phone": {}},
+        "flags": [],
+        "meta": {},
+        "ok": false
+    });
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn prune_removes_empty_containers() {
+    let mut value = parse(SCATTERED).unwrap();
+    prune_nulls(&mut value, true);
+    // profile 清理之后只剩空对象，也一起删除；数组里的 null 保留
+    assert_eq!(value, json!({"id": 7, "tags": [null, "a", {"y": 0}], "ok": false}));
+}
+
+#[test]
+fn prune_leaves_root_alone() {
+    let mut value = json!(null);
+    prune_nulls(&mut value, true);
+    assert_eq!(value, json!(null));
+    let mut value = json!({"a": null});
+    prune_nulls(&mut value, true);
+    assert_eq!(value, json!({}));  // 顶层的空对象本身不会被删除
+}
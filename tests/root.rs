@@ -0,0 +1,28 @@
+//! 顶层的值
+//!
+//! RFC 8259 允许顶层是标量，默认接受；打开 require_root_container 后只接受对象和数组。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, parse};
+use serde_json::json;
+
+#[test]
+fn scalar_root_is_accepted_by_default() {
+    assert_eq!(parse("42").unwrap(), json!(42));
+    assert_eq!(Parser::new().require_root_container(false).parse("42").unwrap(), json!(42));
+    assert_eq!(parse(" \"hi\" ").unwrap(), json!("hi"));
+}
+
+#[test]
+fn scalar_root_is_rejected_when_required() {
+    let parser = Parser::new().require_root_container(true);
+    let err = parser.parse("42").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::RootNotContainer);
+    assert_eq!(err.offset, 0);
+    assert!(!err.incomplete);
+    assert_eq!(parser.parse("  null").unwrap_err().offset, 2);  // 位置指向跳过空白之后的标量
+    assert_eq!(parser.parse("").unwrap_err().kind, ErrorKind::EmptyInput);  // 空输入仍然是 EmptyInput
+    assert_eq!(parser.parse("[42]").unwrap(), json!([42]));  // 容器中的标量不受影响
+    assert_eq!(parser.parse(" {\"a\": 42}").unwrap(), json!({"a": 42}));
+}
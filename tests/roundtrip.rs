@@ -0,0 +1,133 @@
+//! parse 和 to_string 互为逆操作的性质测试
+//!
+//! 随机生成 Value 树，序列化之后再解析回来，要求得到相同的值。
+//! 生成的内容特意包括空字符串、含有各种需要转义的字符的字符串、
+//! 空数组和空对象的嵌套，以及负数、很大的整数和极端的浮点数。
+//! 另外用固定的例子钉住容易出错的边界情况，例如正斜杠的转义和对象成员的顺序。
+
+#![cfg(feature = "serde_json")]
+
+use proptest::prelude::*;
+use serde_json::{Map, Number, Value, json};
+
+/// 字符串：普通字符、需要转义的字符、控制字符和非 ASCII 字符混合出现
+fn string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        Just("\"\\/\u{8}\u{c}\n\r\t".to_string()),  // 所有有简写形式的转义
+        "[\u{0}-\u{1f}\"\\\\a-z é中😀]{0,16}",
+        any::<String>(),
+    ]
+}
+
+/// 数字：整数覆盖 i64 和 u64 的整个范围，浮点数只取有限值
+fn number() -> impl Strategy<Value = Number> {
+    prop_oneof![
+        any::<i64>().prop_map(Number::from),
+        any::<u64>().prop_map(Number::from),
+        Just(Number::from(i64::MIN)),
+        Just(Number::from(u64::MAX)),
+        any::<f64>().prop_filter_map("JSON 无法表示 NaN 和无穷大", Number::from_f64),
+    ]
+}
+
+/// 任意的 Value 树，最多 6 层，数组和对象最多 8 个元素
+fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        number().prop_map(Value::Number),
+        string().prop_map(Value::String),
+    ];
+    leaf.prop_recursive(6, 128, 8, |inner| {
+        prop_oneof![
+            Just(Value::Array(Vec::new())),
+            Just(Value::Object(Map::new())),
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+            prop::collection::btree_map(string(), inner, 0..8).prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn compact_round_trip(v in value()) {
+        let text = json_parser::to_string(&v);
+        prop_assert_eq!(json_parser::parse(&text).unwrap(), v);
+    }
+
+    #[test]
+    fn pretty_round_trip(v in value(), indent in 0usize..5) {
+        let text = json_parser::to_string_pretty(&v, indent);
+        prop_assert_eq!(json_parser::parse(&text).unwrap(), v);
+    }
+
+    #[test]
+    fn validate_accepts_serialized(v in value()) {
+        prop_assert!(json_parser::validate(&json_parser::to_string(&v)).is_ok());
+    }
+}
+
+/// 正斜杠的转义是可选的：解析时 "a/b" 和 "a\/b" 相同，输出时总是不转义
+#[test]
+fn forward_slash() {
+    let bare = json_parser::parse(r#""a/b""#).unwrap();
+    let escaped = json_parser::parse(r#""a\/b""#).unwrap();
+    assert_eq!(bare, json!("a/b"));
+    assert_eq!(escaped, bare);
+    assert_eq!(json_parser::to_string(&escaped), r#""a/b""#);
+    assert_eq!(json_parser::to_string_pretty(&json!({"url": "http://x/y"}), 2), "{\n  \"url\": \"http://x/y\"\n}");
+    assert_eq!(json_parser::parse(&json_parser::to_string(&escaped)).unwrap(), escaped);
+}
+
+/// ascii_only 把非 ASCII 字符写成 \uXXXX，基本多文种平面之外的字符写成代理对
+#[test]
+fn ascii_only() {
+    let ascii = json_parser::PrettyConfig { ascii_only: true, ..json_parser::PrettyConfig::default() };
+    let value = json!({"é": "😀 中"});
+    let text = json_parser::to_string_pretty_with(&json!("😀"), &ascii);
+    assert_eq!(text, r#""\ud83d\ude00""#);
+    let text = json_parser::to_string_pretty_with(&value, &ascii);
+    assert_eq!(text, "{\n  \"\\u00e9\": \"\\ud83d\\ude00 \\u4e2d\"\n}");  // 键也会转义
+    assert!(text.is_ascii());
+    assert_eq!(json_parser::parse(&text).unwrap(), value);
+    let utf8 = json_parser::to_string_pretty_with(&json!("😀"), &json_parser::PrettyConfig::default());
+    assert_eq!(utf8, "\"😀\"");  // 默认直接输出 UTF-8
+}
+
+/// 对象成员保持文档中的顺序，而不是按键排序
+#[test]
+fn key_order() {
+    let value = json_parser::parse(r#"{"b":1,"a":2}"#).unwrap();
+    assert_eq!(json_parser::to_string(&value), r#"{"b":1,"a":2}"#);
+    let nested = r#"{"z":{"y":[{"x":1,"w":2}],"v":null},"u":true}"#;
+    assert_eq!(json_parser::to_string(&json_parser::parse(nested).unwrap()), nested);
+    assert_eq!(json_parser::to_string_canonical(&value), r#"{"a":2,"b":1}"#);  // 需要排序时用 canonical 形式
+}
+
+/// 打开 decimal 后小数按十进制精确保存：0.1 + 0.2 恰好等于 0.3，末尾的 0 也不会丢失
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn decimal_precision() {
+    let parser = json_parser::Parser::new().decimal(true);
+    let value = parser.parse_native(r#"{"a": 0.1, "b": 0.2, "price": 1000.50}"#).unwrap();
+    let decimal = |key| *value.get(key).and_then(|v| v.as_decimal()).unwrap();
+    assert_eq!((decimal("a") + decimal("b")).to_string(), "0.3");
+    let price = decimal("price").to_string();
+    assert_eq!(price, "1000.50");
+    let again = parser.parse_native(&price).unwrap();
+    assert_eq!(again.as_decimal().unwrap().to_string(), "1000.50");
+}
+
+/// 打开 arbitrary_precision 后数字按原文保存，序列化时逐字节原样输出
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn arbitrary_precision() {
+    let parser = json_parser::Parser::new().arbitrary_precision(true);
+    let text = "1234567890.123456789012345";
+    let value = parser.parse(text).unwrap();
+    assert_eq!(json_parser::to_string(&value), text);
+    let nested = r#"{"n":[1234567890.123456789012345,-0.000000000000000000001,1E400]}"#;
+    assert_eq!(json_parser::to_string(&parser.parse(nested).unwrap()), nested);  // 超出 f64 范围的数字也不会变
+    assert_ne!(json_parser::to_string(&json_parser::parse(text).unwrap()), text);  // 默认转换为 f64，会丢失精度
+}
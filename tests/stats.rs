@@ -0,0 +1,30 @@
+//! parse_with_stats
+//!
+//! 针对 tests/sample.json 示例文档检查解析时收集的统计信息。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, max_depth, parse, parse_with_stats, type_histogram};
+
+const SAMPLE: &str = include_str!("sample.json");
+
+#[test]
+fn sample_document_counts() {
+    let (value, stats) = parse_with_stats(SAMPLE).unwrap();
+    assert_eq!(value, parse(SAMPLE).unwrap());
+    assert_eq!((stats.objects, stats.arrays, stats.strings, stats.numbers), (11, 6, 16, 11));
+    assert_eq!(stats.bytes, SAMPLE.len());
+    assert_eq!(stats.max_depth, 4);
+    let counts = type_histogram(&value);  // 与解析之后再遍历一遍的结果一致
+    assert_eq!((counts.objects, counts.arrays, counts.strings, counts.numbers), (stats.objects, stats.arrays, stats.strings, stats.numbers));
+    assert_eq!(max_depth(&value), stats.max_depth);
+}
+
+#[test]
+fn scalars_and_errors() {
+    let (_, stats) = parse_with_stats(" 42 ").unwrap();
+    assert_eq!((stats.numbers, stats.max_depth, stats.bytes), (1, 0, 4));
+    let (_, stats) = parse_with_stats(r#"{"k": [true, null, "v"]}"#).unwrap();
+    assert_eq!((stats.objects, stats.arrays, stats.strings, stats.numbers, stats.max_depth), (1, 1, 1, 0, 2));  // 键不计入 strings
+    assert_eq!(parse_with_stats("[1, 2").unwrap_err().kind, ErrorKind::UnterminatedArray);
+}
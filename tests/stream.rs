@@ -0,0 +1,77 @@
+//! 多个值和分块输入
+//!
+//! parse_many 解析首尾相接或用空白分隔的多个值；
+//! StreamParser 在输入分块到达时逐个产出完整的值。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, StreamParser, parse_many};
+use serde_json::{Value, json};
+
+#[test]
+fn many_without_separator() {
+    assert_eq!(parse_many(r#"{"a":1}{"b":2}"#).unwrap(), [json!({"a": 1}), json!({"b": 2})]);
+    assert_eq!(parse_many("[1][2]\"x\"").unwrap(), [json!([1]), json!([2]), json!("x")]);
+}
+
+#[test]
+fn many_separated_by_whitespace() {
+    assert_eq!(parse_many("1 2 3").unwrap(), [1, 2, 3]);
+    assert_eq!(parse_many(" 1 2\n3 ").unwrap(), [1, 2, 3]);
+    assert_eq!(parse_many("12").unwrap(), [12]);  // 相邻的数字字符属于同一个数字
+    assert!(parse_many("").unwrap().is_empty());
+    assert!(parse_many(" \n ").unwrap().is_empty());
+}
+
+#[test]
+fn many_trailing_partial_value() {
+    let err = parse_many("1 [2").unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::UnterminatedArray, 2));  // 位置指向最后一个值的开头
+    let err = parse_many(r#"{"a":1}{"b""#).unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::UnterminatedObject, 7));
+    assert!(matches!(parse_many("[1][2]x").unwrap_err().kind, ErrorKind::TrailingData(_)));
+}
+
+/// 把 input 每次一个字符地交给 parser，返回每次 feed 的结果
+fn feed_chars(parser: &mut StreamParser, input: &str) -> Vec<Option<Value>> {
+    let mut buf = [0; 4];
+    input.chars().map(|c| parser.feed(c.encode_utf8(&mut buf)).unwrap()).collect()
+}
+
+#[test]
+fn stream_byte_at_a_time() {
+    let mut parser = StreamParser::new();
+    let input = r#"{"a":[1,2]}"#;
+    let results = feed_chars(&mut parser, input);
+    assert!(results[..input.len() - 1].iter().all(Option::is_none));  // 最后一个字符之前都还不完整
+    assert_eq!(results[input.len() - 1], Some(json!({"a": [1, 2]})));
+    assert_eq!(parser.buffered(), "");
+    let results = feed_chars(&mut parser, r#" "x}" [3]"#);
+    assert_eq!(results.into_iter().flatten().collect::<Vec<_>>(), [json!("x}"), json!([3])]);  // 字符串里的括号不算
+}
+
+#[test]
+fn stream_invalid_input_fails_immediately() {
+    let mut parser = StreamParser::new();
+    let err = parser.feed("[1,}").unwrap_err();
+    assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, 2));
+    assert_eq!(parser.buffered(), "");  // 出错之后缓冲区被清空
+    for c in ["[", "1", ","] {
+        assert_eq!(parser.feed(c).unwrap(), None);
+    }
+    assert_eq!(parser.feed("}").unwrap_err().kind, ErrorKind::UnexpectedChar);  // 分块到达时在右括号处发现
+}
+
+#[test]
+fn stream_large_value_in_small_chunks() {
+    let mut parser = StreamParser::new();
+    let input = format!("[{}0]", "{\"k\": [\"v\", 1.5]}, ".repeat(20_000));
+    let mut values = Vec::new();
+    for chunk in input.as_bytes().chunks(3) {
+        values.extend(parser.feed(std::str::from_utf8(chunk).unwrap()).unwrap());  // 输入只有 ASCII 字符
+    }
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].as_array().unwrap().len(), 20_001);
+    assert_eq!(parser.feed("12").unwrap(), None);  // 数字后面可能还有数字
+    assert_eq!(parser.finish().unwrap(), Some(json!(12)));
+}
@@ -0,0 +1,24 @@
+//! 字符串中的控制字符
+//!
+//! U+0000 到 U+001F 必须转义后才能出现在字符串里，原样出现时报告其位置。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, parse};
+
+#[test]
+fn raw_control_characters() {
+    for input in ["\"a\nb\"", "\"a\rb\"", "\"a\tb\"", "\"a\u{0}b\"", "\"a\u{1f}b\""] {
+        let err = parse(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, 2), "{:?}", input);
+    }
+    assert_eq!(parse(r#"{"k": ["ok", "x
+"]}"#).unwrap_err().offset, 15);  // 嵌套在容器里也能定位
+}
+
+#[test]
+fn escaped_control_characters() {
+    assert_eq!(parse(r#""a\nb""#).unwrap(), "a\nb");
+    assert_eq!(parse(r#""\t\r\u0000\u001f""#).unwrap(), "\t\r\u{0}\u{1f}");
+    assert_eq!(parse("\"a\u{7f}b\"").unwrap(), "a\u{7f}b");  // DEL 不在 U+0000 到 U+001F 之内，可以原样出现
+}
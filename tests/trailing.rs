@@ -0,0 +1,26 @@
+//! 尾随数据
+//!
+//! 整个输入必须恰好是一个 JSON 值，值后面只允许出现空白。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, parse};
+use serde_json::json;
+
+#[test]
+fn trailing_data_is_an_error() {
+    let err = parse("123garbage").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::TrailingData(_)), "{:?}", err.kind);
+    assert_eq!(err.offset, 3);  // 指向多余内容的开头
+    let err = parse("{} {}").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::TrailingData(_)), "{:?}", err.kind);
+    assert_eq!(err.offset, 3);  // 第二个对象属于尾随数据
+    assert!(matches!(parse("123 456").unwrap_err().kind, ErrorKind::TrailingData(_)));
+}
+
+#[test]
+fn surrounding_whitespace_is_allowed() {
+    assert_eq!(parse("  {}  ").unwrap(), json!({}));
+    assert_eq!(parse("\n[1]\r\n\t").unwrap(), json!([1]));
+    assert_eq!(parse(" 123 ").unwrap(), json!(123));
+}
@@ -0,0 +1,34 @@
+//! 末尾的逗号
+//!
+//! 打开 allow_trailing_comma 后，数组和对象的最后一个成员之后可以多一个逗号；
+//! 默认关闭，此时错误指向多出来的逗号。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser, parse};
+use serde_json::json;
+
+#[test]
+fn allowed() {
+    let parser = Parser::new().allow_trailing_comma(true);
+    assert_eq!(parser.parse("[1,2,]").unwrap(), json!([1, 2]));
+    assert_eq!(parser.parse(r#"{"a":1,}"#).unwrap(), json!({"a": 1}));
+    assert_eq!(parser.parse("[ [], { \"b\": [0 , ] } , ]").unwrap(), json!([[], {"b": [0]}]));  // 逗号前后可以有空白
+}
+
+#[test]
+fn rejected_by_default() {
+    for (input, offset) in [("[1,2,]", 4), (r#"{"a":1,}"#, 6)] {
+        let err = parse(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, offset), "{}", input);
+    }
+}
+
+#[test]
+fn only_one_comma() {
+    let parser = Parser::new().allow_trailing_comma(true);
+    for (input, offset) in [("[1,,]", 3), ("[,]", 1), ("{,}", 1)] {
+        let err = parser.parse(input).unwrap_err();  // 空容器里的逗号和连续的逗号仍然不合法
+        assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, offset), "{}", input);
+    }
+}
@@ -0,0 +1,34 @@
+//! 数字的校验函数
+//!
+//! Parser::with_number_validator 在解析的同一遍中检查每个数字，拒绝时返回 RejectedNumber。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, Parser};
+use serde_json::{Number, json};
+
+/// 只接受 i32 范围内的整数
+fn i32_only(n: &Number) -> Result<(), String> {
+    match n.as_i64() {
+        Some(i) if i32::try_from(i).is_ok() => Ok(()),
+        _ => Err(format!("{} 超出 i32 的范围", n)),
+    }
+}
+
+#[test]
+fn rejects_number_above_i32_max() {
+    let parser = Parser::new().with_number_validator(i32_only);
+    let err = parser.parse(r#"{"id": 2147483648}"#).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::RejectedNumber("2147483648 超出 i32 的范围".to_string()));
+    assert_eq!(err.offset, 7);  // 位置指向数字的开头
+    assert_eq!(parser.parse("  2147483648").unwrap_err().offset, 2);  // 顶层的数字同样经过校验
+    assert_eq!(parser.parse("[1.5]").unwrap_err().kind, ErrorKind::RejectedNumber("1.5 超出 i32 的范围".to_string()));
+}
+
+#[test]
+fn accepts_numbers_in_range() {
+    let parser = Parser::new().with_number_validator(i32_only);
+    assert_eq!(parser.parse("[2147483647, -2147483648, 0]").unwrap(), json!([2147483647, -2147483648, 0]));
+    assert_eq!(parser.parse(r#"{"2147483648": "x"}"#).unwrap(), json!({"2147483648": "x"}));  // 键不是数字
+    assert_eq!(Parser::new().parse("2147483648").unwrap(), json!(2147483648u64));  // 默认不做校验
+}
@@ -0,0 +1,58 @@
+//! parse_with_visitor
+//!
+//! 事件按文本中的顺序发出，语法规则和错误与 parse 相同。
+
+use json_parser::{ErrorKind, Scalar, Visitor, parse_with_visitor};
+
+/// 把事件记录成字符串
+#[derive(Default)]
+struct Events(Vec<String>);
+
+impl<'a> Visitor<'a> for Events {
+    fn start_object(&mut self) {
+        self.0.push("{".into());
+    }
+
+    fn key(&mut self, key: std::borrow::Cow<'a, str>) {
+        self.0.push(format!("key {}", key));
+    }
+
+    fn end_object(&mut self) {
+        self.0.push("}".into());
+    }
+
+    fn start_array(&mut self) {
+        self.0.push("[".into());
+    }
+
+    fn end_array(&mut self) {
+        self.0.push("]".into());
+    }
+
+    fn scalar(&mut self, scalar: Scalar<'a>) {
+        self.0.push(format!("{:?}", scalar));
+    }
+}
+
+#[test]
+fn events_in_document_order() {
+    let mut events = Events::default();
+    parse_with_visitor(r#"{"a": [1, true], "b": null}"#, &mut events).unwrap();
+    assert_eq!(events.0, ["{", "key a", "[", "Number(\"1\")", "Bool(true)", "]", "key b", "Null", "}"]);
+}
+
+#[test]
+fn empty_input() {
+    for input in ["", "   ", "\n\t", "\u{FEFF}"] {
+        let mut events = Events::default();
+        let err = parse_with_visitor(input, &mut events).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::EmptyInput, "{:?}", input);
+        assert!(events.0.is_empty());
+    }
+}
+
+#[test]
+fn errors_match_parse() {
+    assert_eq!(parse_with_visitor("[1,}", &mut Events::default()).unwrap_err().kind, ErrorKind::UnexpectedChar);
+    assert_eq!(parse_with_visitor("[", &mut Events::default()).unwrap_err().kind, ErrorKind::UnterminatedArray);
+}
@@ -0,0 +1,32 @@
+//! 字节顺序标记和空白
+//!
+//! 输入开头的一个 U+FEFF 被跳过，其他位置的 U+FEFF 不是空白。
+//! JSON 的空白只有空格、制表符、换行和回车，其他 Unicode 空白字符出现在记号之间都是错误。
+
+#![cfg(feature = "serde_json")]
+
+use json_parser::{ErrorKind, parse, parse_bytes};
+use serde_json::json;
+
+#[test]
+fn leading_bom() {
+    assert_eq!(parse("\u{FEFF}{}").unwrap(), json!({}));
+    assert_eq!(parse_bytes(b"\xEF\xBB\xBF[1]").unwrap(), json!([1]));
+    assert_eq!(parse("[\"\u{FEFF}\"]").unwrap(), json!(["\u{FEFF}"]));  // 字符串里的 U+FEFF 原样保留
+    assert_eq!(parse("\u{FEFF}").unwrap_err().kind, ErrorKind::EmptyInput);
+    let err = parse("\u{FEFF}\u{FEFF}{}").unwrap_err();  // 只跳过一个
+    assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, 3));
+    assert_eq!(parse(" \u{FEFF}{}").unwrap_err().offset, 1);  // 不在开头时不是字节顺序标记
+}
+
+#[test]
+fn unicode_whitespace_between_tokens() {
+    for (input, offset) in [("\u{A0}1", 0), ("{\"a\":\u{A0}1}", 5), ("\u{2003}[]", 0)] {
+        let err = parse(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (ErrorKind::UnexpectedChar, offset), "{:?}", input);
+    }
+    assert_eq!(parse("[1,\u{A0}2]").unwrap_err().kind, ErrorKind::UnexpectedChar);
+    assert!(matches!(parse("1\u{A0}").unwrap_err().kind, ErrorKind::TrailingData(_)));
+    assert_eq!(parse("[\"\u{A0}\"]").unwrap(), json!(["\u{A0}"]));  // 字符串里可以出现
+    assert_eq!(parse(" \t\r\n[ \t\r\n1 \t\r\n] \t\r\n").unwrap(), json!([1]));
+}